@@ -0,0 +1,64 @@
+//! Counts the allocations of asking many text values.
+//!
+//! Run with `cargo bench --bench allocations`.
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+struct Counting;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+const VALUES: usize = 1_000;
+
+fn count(f: impl FnOnce()) -> usize {
+    let start = ALLOCATIONS.load(Ordering::Relaxed);
+    f();
+    ALLOCATIONS.load(Ordering::Relaxed) - start
+}
+
+fn main() {
+    let input = "some text\n".repeat(VALUES);
+
+    let with_ask = count(|| {
+        for _ in 0..VALUES {
+            asking::text()
+                .reader("some text\n".as_bytes())
+                .writer(async_std::io::sink())
+                .ask_and_wait()
+                .unwrap();
+        }
+    });
+
+    let with_buffer = count(|| {
+        let mut question = asking::text()
+            .reader(input.as_bytes())
+            .writer(async_std::io::sink());
+        let mut buffer = String::new();
+        async_std::task::block_on(async {
+            for _ in 0..VALUES {
+                question.ask_into_buffer(&mut buffer).await.unwrap();
+            }
+        });
+    });
+
+    println!("Allocations for {} text values", VALUES);
+    println!("  ask:             {:>8}", with_ask);
+    println!("  ask_into_buffer: {:>8}", with_buffer);
+}
@@ -8,14 +8,14 @@ fn main() {
     let awaited_date = async_std::task::block_on(
         asking::date()
             .message("Please input your awaited date: ")
-            .min(Local::today().naive_local())
+            .min(Local::now().date_naive())
             .help("Use a %Y-%m-%d format please.\n")
             .feedback(|_| "Thank you!".to_string())
             .ask(),
     )
     .expect("Failed to read line");
 
-    let offset = awaited_date.signed_duration_since(Local::today().naive_local());
+    let offset = awaited_date.signed_duration_since(Local::now().date_naive());
     println!(
         "There are {} weeks, and {} days left!",
         offset.num_weeks(),
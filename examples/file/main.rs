@@ -14,7 +14,7 @@ async fn main() -> eyre::Result<()> {
     {
         let mut file = File::create("examples\\file\\in.txt")?;
         file.set_len(0)?; // Clean the file
-        write!(file, "{}", "false")?;
+        write!(file, "false")?;
         File::create("examples\\file\\out.txt")?;
     }
 
@@ -33,7 +33,7 @@ async fn main() -> eyre::Result<()> {
         .writer(file_out)
         .message("Shall I continue?\n")
         .repeat_help("Please use y/n format.\n")
-        .str_test_with_msg(|s| s.len() > 0, "You can do it!")
+        .str_test_with_msg(|s| !s.is_empty(), "You can do it!")
         .ask();
 
     match question.await {
@@ -1,13 +1,13 @@
-//! Require the input to be an element of an iterator.
+//! Require the input to be inside a range.
 //!
 //! This examples shows how to use:
-//! - `select`
+//! - `inside_range_with_msg`
 
 fn main() {
     let level: u32 = async_std::task::block_on(
         asking::question()
             .message("What level is your Pokemon?")
-            .inside_with_msg(
+            .inside_range_with_msg(
                 5..=100,
                 "It should be at least level 5 and most 100, right? Try again.",
             )
@@ -17,5 +17,5 @@ fn main() {
     )
     .expect("Failed to read line");
 
-    assert!(level <= 100 && level >= 5);
+    assert!((5..=100).contains(&level));
 }
@@ -0,0 +1,20 @@
+//! Input a date while seeing how it is understood!
+//!
+//! This examples shows how to use `live_preview`.
+
+use chrono::NaiveDate;
+
+fn main() {
+    let date = async_std::task::block_on(
+        asking::date()
+            .message("Please input a date: ")
+            .live_preview(|s| match NaiveDate::parse_from_str(s, "%Y-%m-%d") {
+                Ok(date) => date.format("%A, %-d %B %Y").to_string(),
+                Err(_) => "Use a %Y-%m-%d format please.".to_string(),
+            })
+            .ask(),
+    )
+    .expect("Failed to read line");
+
+    println!("You chose {}.", date.format("%A"));
+}
@@ -0,0 +1,15 @@
+//! Edit a suggested answer, like in a shell!
+//!
+//! This examples shows how to use `prefill`.
+
+fn main() {
+    let name = async_std::task::block_on(
+        asking::text()
+            .message("Project name: ")
+            .prefill("my-project")
+            .ask(),
+    )
+    .expect("Failed to read line");
+
+    println!("Creating {}.", name);
+}
@@ -0,0 +1,32 @@
+//! Ask through a prompt function, like the browser's `window.prompt`.
+//!
+//! This examples shows how to use `prompt_io`,
+//! emulating prompt dialogs with the standard input and output.
+
+use std::io::Write;
+
+fn main() {
+    let (reader, writer) = asking::prompt_io(
+        |message| {
+            print!("[prompt] {}", message);
+            std::io::stdout().flush().ok()?;
+            let mut line = String::new();
+            match std::io::stdin().read_line(&mut line) {
+                Ok(0) | Err(_) => None,
+                Ok(_) => Some(line),
+            }
+        },
+        |output| println!("[log] {}", output),
+    );
+    let answer = asking::question::<u8>()
+        .message("How old are you? ")
+        .help("Please, use a number.\n")
+        .feedback(|age| format!("You are {} years old.", age))
+        .reader(reader)
+        .writer(writer)
+        .ask_and_wait();
+
+    if answer.is_err() {
+        eprintln!("Error with questionnaire, try again later.");
+    }
+}
@@ -0,0 +1,32 @@
+//! Ask for a period of time, where the end has to come after the start.
+//!
+//! This examples shows how to use:
+//! - `Questionnaire`
+//! - `test_with_context`
+
+use asking::Questionnaire;
+use chrono::NaiveDate;
+
+fn main() {
+    let mut questionnaire = Questionnaire::new();
+    let period = async_std::task::block_on(async {
+        let start: NaiveDate = questionnaire
+            .ask("start", asking::date().message("Start date: "))
+            .await?;
+        let end: NaiveDate = questionnaire
+            .ask(
+                "end",
+                asking::date().message("End date: ").test_with_context(
+                    |end: &NaiveDate, context| Some(*end) > context.get("start"),
+                    "The end date must come after the start date.",
+                ),
+            )
+            .await?;
+        Ok::<_, asking::error::ProcessingError>((start, end))
+    });
+
+    match period {
+        Ok((start, end)) => println!("{} days!", end.signed_duration_since(start).num_days()),
+        Err(_) => eprintln!("Error with questionnaire, try again later."),
+    }
+}
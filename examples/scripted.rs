@@ -0,0 +1,16 @@
+//! Replay answers from a script, for demos without typing.
+//!
+//! This examples shows how to use `scripted`.
+
+fn main() {
+    let answer = asking::text()
+        .scripted(vec!["bad", "good"])
+        .message("How are you? ")
+        .not_with_msg("bad".to_string(), "I hope not! Try again.")
+        .feedback(|answer| format!("I am {} too!\n", answer))
+        .ask_and_wait();
+
+    if answer.is_err() {
+        eprintln!("Error with questionnaire, try again later.");
+    }
+}
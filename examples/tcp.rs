@@ -0,0 +1,37 @@
+//! Ask over a TCP connection!
+//!
+//! This examples shows how to use `QuestionBuilder::duplex`,
+//! with a server asking a client connected through the loopback interface.
+//! The client relays the standard input to the server and the answers to the standard output.
+
+use async_std::{
+    io,
+    net::{SocketAddr, TcpListener, TcpStream},
+    task,
+};
+
+fn main() -> eyre::Result<()> {
+    task::block_on(async {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let client = task::spawn(client(listener.local_addr()?));
+
+        let (stream, _) = listener.accept().await?;
+        let name: String = asking::QuestionBuilder::duplex(stream)
+            .message("What is your name? ")
+            .feedback(|name| format!("Hello, {}!\n", name))
+            .ask()
+            .await?;
+
+        client.await?;
+        println!("The server greeted {}.", name);
+        Ok(())
+    })
+}
+
+async fn client(address: SocketAddr) -> io::Result<()> {
+    let stream = TcpStream::connect(address).await?;
+    let mut writer = stream.clone();
+    task::spawn(async move { io::copy(io::stdin(), &mut writer).await });
+    io::copy(stream, &mut io::stdout()).await?;
+    Ok(())
+}
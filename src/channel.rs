@@ -0,0 +1,159 @@
+use async_std::{
+    channel::{Receiver, Sender},
+    io::{Read, Write},
+    stream::Stream,
+    task::{Context, Poll},
+};
+use std::{future::Future, pin::Pin};
+
+type Sending = Pin<Box<dyn Future<Output = std::io::Result<()>> + Send + Sync>>;
+
+/// Reader that takes each message of a channel as a line of input.
+///
+/// Useful to drive questions programmatically, for example, from a GUI front-end.
+/// A newline is appended to messages that do not end with one.
+/// When the channel is closed and empty, the reader gets to the end of file.
+///
+/// # Examples
+///
+/// ```
+/// # async_std::task::block_on(async {
+/// let (sender, receiver) = async_std::channel::unbounded();
+/// sender.send("yes".to_string()).await.unwrap();
+/// let answer = asking::yn()
+///     .reader(asking::ChannelReader::new(receiver))
+///     .ask()
+///     .await
+///     .unwrap();
+/// assert!(answer);
+/// # });
+/// ```
+#[derive(Debug)]
+pub struct ChannelReader {
+    receiver: Pin<Box<Receiver<String>>>,
+    line: Vec<u8>,
+    position: usize,
+}
+
+impl ChannelReader {
+    /// Constructs a new `ChannelReader` from the receiving half of a channel.
+    pub fn new(receiver: Receiver<String>) -> Self {
+        ChannelReader {
+            receiver: Box::pin(receiver),
+            line: Vec::new(),
+            position: 0,
+        }
+    }
+}
+
+impl Read for ChannelReader {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<std::io::Result<usize>> {
+        if self.position == self.line.len() {
+            match self.receiver.as_mut().poll_next(cx) {
+                Poll::Pending => return Poll::Pending,
+                Poll::Ready(None) => return Poll::Ready(Ok(0)),
+                Poll::Ready(Some(mut message)) => {
+                    if !message.ends_with('\n') {
+                        message.push('\n');
+                    }
+                    self.line = message.into_bytes();
+                    self.position = 0;
+                }
+            }
+        }
+        let this = &mut *self;
+        let n = buf.len().min(this.line.len() - this.position);
+        buf[..n].copy_from_slice(&this.line[this.position..this.position + n]);
+        this.position += n;
+        Poll::Ready(Ok(n))
+    }
+}
+
+/// Writer that delivers output to a channel, one message per flush.
+///
+/// Useful to let a front-end, for example a GUI, render the output of questions.
+/// Writes are buffered until the writer is flushed, which questions do after each message.
+///
+/// # Examples
+///
+/// ```
+/// # async_std::task::block_on(async {
+/// let (sender, receiver) = async_std::channel::unbounded();
+/// let _answer = asking::yn()
+///     .message("Continue? ")
+///     .reader("yes\n".as_bytes())
+///     .writer(asking::ChannelWriter::new(sender))
+///     .ask()
+///     .await
+///     .unwrap();
+/// assert_eq!(receiver.recv().await.unwrap(), "Continue? ");
+/// # });
+/// ```
+pub struct ChannelWriter {
+    sender: Sender<String>,
+    buffer: Vec<u8>,
+    sending: Option<Sending>,
+}
+
+impl ChannelWriter {
+    /// Constructs a new `ChannelWriter` from the sending half of a channel.
+    pub fn new(sender: Sender<String>) -> Self {
+        ChannelWriter {
+            sender,
+            buffer: Vec::new(),
+            sending: None,
+        }
+    }
+}
+
+impl std::fmt::Debug for ChannelWriter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ChannelWriter")
+            .field("sender", &self.sender)
+            .field("buffer", &self.buffer)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Write for ChannelWriter {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        _: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<std::io::Result<usize>> {
+        self.buffer.extend_from_slice(buf);
+        Poll::Ready(Ok(buf.len()))
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        if self.sending.is_none() {
+            if self.buffer.is_empty() {
+                return Poll::Ready(Ok(()));
+            }
+            let message = String::from_utf8_lossy(&std::mem::take(&mut self.buffer)).into_owned();
+            let sender = self.sender.clone();
+            self.sending = Some(Box::pin(async move {
+                sender
+                    .send(message)
+                    .await
+                    .map_err(|_| std::io::ErrorKind::BrokenPipe.into())
+            }));
+        }
+        let result = match self.sending.as_mut() {
+            Some(sending) => sending.as_mut().poll(cx),
+            None => Poll::Ready(Ok(())),
+        };
+        if result.is_ready() {
+            self.sending = None;
+        }
+        result
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        self.poll_flush(cx)
+    }
+}
@@ -2,8 +2,22 @@ use thiserror::Error;
 
 /// All possible errors of the [`ask`] method.
 ///
+/// It implements `std::error::Error`, so it converts into an [`eyre::Report`] with `?`.
+///
+/// # Examples
+///
+/// ```no_run
+/// async fn confirm() -> eyre::Result<bool> {
+///     let answer = asking::yn().message("Continue? ").ask().await?;
+///     Ok(answer)
+/// }
+/// # async_std::task::block_on(confirm()).unwrap();
+/// ```
+///
 /// [`ask`]: ../struct.QuestionBuilder.html#method.ask
+/// [`eyre::Report`]: https://docs.rs/eyre/0.6.5/eyre/struct.Report.html
 #[derive(Error, Debug)]
+#[non_exhaustive]
 pub enum ProcessingError {
     /// The user has no more attempts to answer a question.
     ///
@@ -23,16 +37,210 @@ pub enum ProcessingError {
     /// # Remarks
     ///
     /// This is particularly useful when reading from a file.
+    /// A last line without a trailing newline is still processed as input,
+    /// so this is only returned once there is nothing left to read.
     #[error("EOF reached while asking for input.")]
     Eof,
+    /// The reader was disconnected while reading input,
+    /// for example, by a broken pipe or a connection reset.
+    ///
+    /// # Remarks
+    ///
+    /// Unlike [`Eof`], where the peer closed cleanly, this signals an unexpected interruption.
+    ///
+    /// [`Eof`]: #variant.Eof
+    #[error("The reader was disconnected while asking for input.")]
+    Disconnected { source: std::io::Error },
+    /// The future given to [`cancel_on`] completed before the question was answered.
+    ///
+    /// [`cancel_on`]: ../struct.QuestionBuilder.html#method.cancel_on
+    #[error("Asking was cancelled.")]
+    Cancelled,
+    /// The only attempt to answer the question was invalid.
+    ///
+    /// Related to the method [`ask_once`].
+    /// The `reason` says whether the input failed parsing or a test, and why.
+    ///
+    /// [`ask_once`]: ../struct.QuestionBuilder.html#method.ask_once
+    #[error("The input was rejected, {reason}")]
+    Rejected { reason: String },
+    /// The line that stops asking was read.
+    ///
+    /// Related to the method [`abort_keyword`].
+    ///
+    /// [`abort_keyword`]: ../struct.QuestionBuilder.html#method.abort_keyword
+    #[error("Asking was aborted.")]
+    Aborted,
+    /// More input than allowed was read while asking.
+    ///
+    /// Related to the method [`session_budget`].
+    ///
+    /// [`session_budget`]: ../struct.QuestionBuilder.html#method.session_budget
+    #[error("More than {limit} bytes were read while asking for input.")]
+    InputTooLong { limit: usize },
+    /// The answer could not be read from a file.
+    ///
+    /// Related to the method [`secret_from_path`].
+    ///
+    /// [`secret_from_path`]: ../struct.QuestionBuilder.html#method.secret_from_path
+    #[error("Could not read the answer from `{}`.", path.display())]
+    SecretUnreadable {
+        path: std::path::PathBuf,
+        source: std::io::Error,
+    },
+    /// The answer read from a file is not valid.
+    ///
+    /// Related to the method [`secret_from_path`].
+    ///
+    /// [`secret_from_path`]: ../struct.QuestionBuilder.html#method.secret_from_path
+    #[error("The answer in `{}` is not valid: {reason}", path.display())]
+    InvalidSecret {
+        path: std::path::PathBuf,
+        reason: String,
+    },
+    /// There is no default value to return without reading input.
+    ///
+    /// Related to the method [`dry_run`].
+    ///
+    /// [`dry_run`]: ../struct.QuestionBuilder.html#method.dry_run
+    #[error("There is no default value to answer the question without input.")]
+    NoDefault,
+    /// The input can not be masked, because the reader or writer is not a terminal.
+    ///
+    /// Related to the method [`require_tty_for_mask`].
+    ///
+    /// [`require_tty_for_mask`]: ../struct.QuestionBuilder.html#method.require_tty_for_mask
+    #[error("Input can not be masked outside a terminal.")]
+    MaskUnavailable,
+    /// The confirmation did not match the input, even after all retries.
+    ///
+    /// Related to the method [`confirm_retries`].
+    ///
+    /// [`confirm_retries`]: ../struct.QuestionBuilder.html#method.confirm_retries
+    #[error("The confirmation did not match the input.")]
+    ConfirmationMismatch,
     /// The time to answer a question has passed.
     ///
     /// Related to the method [`timeout`].
+    /// The `duration` is the time that was given, like the one set by [`timeout`].
     ///
     /// [`timeout`]: ../struct.QuestionBuilder.html#method.timeout
-    #[error("User could not answer the question in the given time.")]
+    #[error("User could not answer the question in the given time ({duration:?}).")]
     Timeout {
-        #[from]
         source: async_std::future::TimeoutError,
+        duration: std::time::Duration,
+    },
+}
+
+/// All possible errors while parsing key-value input.
+///
+/// Related to the pattern [`key_values`].
+///
+/// [`key_values`]: ../fn.key_values.html
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum KeyValueError {
+    /// An entry does not contain the separator between key and value.
+    #[error("Entry `{entry}` is not of the form `key{separator}value`.")]
+    MissingSeparator { entry: String, separator: String },
+    /// The same key was given more than once.
+    #[error("Key `{key}` was given more than once.")]
+    DuplicateKey { key: String },
+}
+
+/// All possible errors while parsing a line of text.
+///
+/// Related to the pattern [`text_line`].
+///
+/// [`text_line`]: ../fn.text_line.html
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum TextLineError {
+    /// The input contains a control character, like a tab.
+    #[error("The input can not contain control characters.")]
+    ControlCharacter,
+    /// The input is empty or contains only whitespace.
+    #[error("The input can not be blank.")]
+    Blank,
+}
+
+/// All possible errors while choosing an option.
+///
+/// Related to the pattern [`select`].
+///
+/// [`select`]: ../fn.select.html
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum SelectError {
+    /// The input does not refer to any option.
+    #[error("Input `{input}` is not one of the options.")]
+    Unknown { input: String },
+    /// The input is a prefix of more than one option.
+    #[error("Input `{input}` is ambiguous, it could be: {}.", candidates.join(", "))]
+    Ambiguous {
+        input: String,
+        candidates: Vec<String>,
+    },
+    /// The input is not one of the keywords.
+    #[error("Input `{input}` is not one of: {}.", keywords.join(", "))]
+    UnknownKeyword {
+        input: String,
+        keywords: Vec<String>,
     },
 }
+
+/// All possible errors while parsing a URL.
+///
+/// Related to the pattern [`url`].
+///
+/// [`url`]: ../fn.url.html
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum UrlError {
+    /// The input does not start with a scheme, like `https:`.
+    #[error("Input `{input}` is missing a scheme, like `https://`.")]
+    MissingScheme { input: String },
+    /// The scheme contains characters other than letters, digits, `+`, `-` and `.`,
+    /// or does not start with a letter.
+    #[error("Scheme `{scheme}` is not valid.")]
+    InvalidScheme { scheme: String },
+    /// There is nothing after `//`, where the host is expected.
+    #[error("The URL is missing a host after `//`.")]
+    MissingHost,
+    /// The input contains whitespace, which must be percent-encoded as `%20`.
+    #[error("The URL can not contain whitespace, use `%20` instead.")]
+    Whitespace,
+}
+
+/// All possible contradictions in the configuration of a question.
+///
+/// Related to the method [`validate_config`].
+///
+/// [`validate_config`]: ../struct.QuestionBuilder.html#method.validate_config
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ConfigError {
+    /// There are zero attempts, so no input is ever read.
+    #[error("There are no attempts to answer the question.")]
+    NoAttempts,
+    /// The time to answer is zero, so the question always times out.
+    #[error("There is no time to answer the question.")]
+    NoTime,
+    /// The input is required, so the default value is never used.
+    #[error("The input is required, so the default value is never used.")]
+    RequiredWithDefault,
+    /// Input must be masked, but the reader and writer are not a terminal.
+    #[error("Input can not be masked outside a terminal.")]
+    MaskUnavailable,
+    /// Some options can never be accepted, because they fail a test.
+    #[error("Options {} can never be accepted.", options.join(", "))]
+    InvalidOptions { options: Vec<String> },
+    /// The input is required, but the reader is already at the end of file.
+    ///
+    /// Related to the method [`validate_config_with_reader`].
+    ///
+    /// [`validate_config_with_reader`]: ../struct.QuestionBuilder.html#method.validate_config_with_reader
+    #[error("The input is required, but there is nothing left to read.")]
+    RequiredAtEof,
+    /// The input encoding is not UTF-8, but input is read with [`idle_timeout`]
+    /// or edited in a terminal, which only read UTF-8.
+    ///
+    /// [`idle_timeout`]: ../struct.QuestionBuilder.html#method.idle_timeout
+    #[error("Input can only be read as UTF-8 with an idle timeout or a line editor.")]
+    EncodingUnsupported,
+}
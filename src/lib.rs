@@ -12,7 +12,17 @@
 //!   - **[`yn`]** - yes/no questions.
 //!   - **[`date`]** - dates in `%Y-%m-%d` format.
 //!   - **[`select`]** - choose one option.
+//!   - **[`select_listed`]** - choose one option, possibly by an unambiguous prefix.
+//!   - **[`number_in_range`]** - a number between two bounds.
+//!   - **[`select_map`]** - choose a value of any type by its keyword.
+//!   - **[`select_normalized`]** - choose one option, ignoring case and whitespace.
 //!   - **[`text`]** - just a String.
+//!   - **[`text_line`]** - a String without control characters.
+//!   - **[`nonempty_text`]** - a [`NonEmptyString`], trimmed and not empty.
+//!   - **[`password`]** - a String that is not echoed.
+//!   - **[`key_values`]** - a map from input like `a=1 b=2`.
+//!   - **[`pause`]** - wait until the user presses Enter.
+//!   - **[`url`]** - an absolute URL, like `https://example.com`.
 //!   - **[`T`]** - your own type! (implementing or not the trait [`FromStr`]).
 //! - **Cross-platform** - Generic on [`reader`] and [`writer`]!
 //! - **[`Help`] messages** - Help the user to input a correct answer.
@@ -20,11 +30,23 @@
 //! - **[Default values]** - Add a value for empty inputs.
 //! - **Standardized [`error`] handling** - You can manage errors!
 //! - **[`Feedback`]** - Display a final message depending on the accepted value.
+//! - **[`Questionnaire`]s** - Ask questions that depend on previous answers.
 //!
 //! [Asynchronous]: struct.QuestionBuilder.html#method.ask
 //! [`yn`]: fn.yn.html
 //! [`date`]: fn.date.html
 //! [`select`]: fn.select.html
+//! [`select_listed`]: fn.select_listed.html
+//! [`number_in_range`]: fn.number_in_range.html
+//! [`select_map`]: fn.select_map.html
+//! [`select_normalized`]: fn.select_normalized.html
+//! [`text_line`]: fn.text_line.html
+//! [`nonempty_text`]: fn.nonempty_text.html
+//! [`NonEmptyString`]: struct.NonEmptyString.html
+//! [`key_values`]: fn.key_values.html
+//! [`password`]: fn.password.html
+//! [`pause`]: fn.pause.html
+//! [`url`]: fn.url.html
 //! [`T`]: struct.QuestionBuilder.html#method.new
 //! [`FromStr`]: https://doc.rust-lang.org/core/str/trait.FromStr.html
 //! [`reader`]: struct.QuestionBuilder.html#method.reader
@@ -34,6 +56,19 @@
 //! [Default values]: struct.QuestionBuilder.html#method.default_value
 //! [`error`]: error/enum.Processing.html
 //! [`Feedback`]: struct.QuestionBuilder.html#method.feedback
+//! [`Questionnaire`]: struct.Questionnaire.html
+//!
+//! # Feature flags
+//!
+//! - **`std`** (default) - Questions in the standard input and output ([`StdQuestionBuilder`])
+//!   and all patterns, like [`yn`] and [`date`].
+//!   Without it, [`QuestionBuilder`] is still available with your own [`reader`] and [`writer`],
+//!   together with its tests, [`Questionnaire`]s and channel I/O.
+//!   The crate itself is not `no_std` yet, since its I/O traits come from `async-std`.
+//! - **`strum`** - The pattern `select_enum`, to choose a variant of an enum.
+//!
+//! [`StdQuestionBuilder`]: type.StdQuestionBuilder.html
+//! [`QuestionBuilder`]: struct.QuestionBuilder.html
 //!
 //! # Quick example
 //!
@@ -63,10 +98,30 @@
 #[cfg(doctest)]
 doc_comment::doctest!("../README.md");
 
+mod channel;
 /// Errors while asking a question.
 pub mod error;
+mod non_empty;
 mod pattern;
+mod prompt;
 mod question;
+mod questionnaire;
 
-pub use pattern::{date, question, select, select_with_msg, text, yn};
-pub use question::{QuestionBuilder, StdQuestionBuilder};
+pub use channel::{ChannelReader, ChannelWriter};
+pub use non_empty::NonEmptyString;
+#[cfg(all(feature = "std", feature = "strum"))]
+pub use pattern::select_enum;
+#[cfg(feature = "std")]
+pub use pattern::{
+    date, key_values, nonempty_text, number_in_range, password, pause, question, select,
+    select_labeled, select_listed, select_map, select_normalized, select_with_msg, text, text_line,
+    url, yn,
+};
+pub use prompt::{prompt_io, PromptReader, PromptWriter};
+#[cfg(feature = "std")]
+pub use question::StdQuestionBuilder;
+pub use question::{
+    Cancellation, DefaultSource, Encoding, Executor, Integer, Locale, Progress, PromptDescriptor,
+    QuestionBuilder, QuestionConfig, QuestionParts, RetryPolicy, TestMode,
+};
+pub use questionnaire::{combine, combine_with_msg, Context, Questionnaire};
@@ -0,0 +1,73 @@
+use std::{fmt, ops::Deref, str::FromStr};
+
+use crate::error::TextLineError;
+
+/// String that is not empty and has no surrounding whitespace.
+///
+/// Parsing trims the input and rejects it if nothing is left,
+/// so code using the value does not need to check it again.
+///
+/// Related to the pattern [`nonempty_text`].
+///
+/// # Examples
+///
+/// ```
+/// use asking::NonEmptyString;
+///
+/// let name: NonEmptyString = "  Ferris  ".parse().unwrap();
+/// assert_eq!(name.as_str(), "Ferris");
+/// assert!("   ".parse::<NonEmptyString>().is_err());
+/// ```
+///
+/// [`nonempty_text`]: fn.nonempty_text.html
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NonEmptyString(String);
+
+impl NonEmptyString {
+    /// Returns the string as a `&str`.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns the inner `String`.
+    pub fn into_string(self) -> String {
+        self.0
+    }
+}
+
+impl FromStr for NonEmptyString {
+    type Err = TextLineError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match input.trim() {
+            "" => Err(TextLineError::Blank),
+            trimmed => Ok(NonEmptyString(trimmed.to_string())),
+        }
+    }
+}
+
+impl Deref for NonEmptyString {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<str> for NonEmptyString {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for NonEmptyString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl From<NonEmptyString> for String {
+    fn from(value: NonEmptyString) -> Self {
+        value.0
+    }
+}
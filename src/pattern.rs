@@ -1,13 +1,22 @@
-use crate::StdQuestionBuilder;
+use crate::{
+    error::{KeyValueError, SelectError, TextLineError, UrlError},
+    Locale,
+};
+#[cfg(feature = "std")]
+use crate::{NonEmptyString, StdQuestionBuilder};
+#[cfg(feature = "std")]
 use chrono::naive::NaiveDate;
-use std::{error::Error, str::FromStr};
+use std::collections::HashMap;
+#[cfg(feature = "std")]
+use std::{error::Error, fmt::Display, str::FromStr};
 
 /// Question for types implementing `FromStr` trait.
 ///
 /// # Remarks
 ///
 /// For types that do not implement `FromStr`, prefer `StdQuestionBuilder::from(&parser)`.
-pub fn question<T: FromStr>() -> StdQuestionBuilder<T>
+#[cfg(feature = "std")]
+pub fn question<T>() -> StdQuestionBuilder<T>
 where
     T: FromStr + Send + Sync,
     <T as FromStr>::Err: Send + Sync + Error + 'static,
@@ -15,17 +24,90 @@ where
     StdQuestionBuilder::default()
 }
 
+/// Question for a number between `lower_bound` and `upper_bound`, including borders.
+///
+/// The help and the error upon an out-of-range value mention both bounds.
+///
+/// # Examples
+///
+/// ```no_run
+/// let guess: u8 = asking::number_in_range(1, 100)
+///     .repeat_message("Please input your guess: ")
+///     .ask_and_wait()
+///     .unwrap();
+/// ```
+#[cfg(feature = "std")]
+pub fn number_in_range<T>(lower_bound: T, upper_bound: T) -> StdQuestionBuilder<T>
+where
+    T: FromStr + PartialOrd + Display + Send + Sync + 'static,
+    <T as FromStr>::Err: Send + Sync + Error + 'static,
+{
+    let help = format!(
+        "Enter a number between {} and {}.\n",
+        lower_bound, upper_bound
+    );
+    let message = format!(
+        "The value must be between {} and {}.",
+        lower_bound, upper_bound
+    );
+    question()
+        .help(help)
+        .describe_bounds(&lower_bound, &upper_bound)
+        .min_max_with_msg(lower_bound, upper_bound, message)
+}
+
 /// Yes/No questions.
 ///
 /// The default parser reads, after making lowercase, the following:
 /// - `true`: "true" or "t" or "yes" or "y"
 /// - `false`: "false" or "f" or "no" or "n"
+#[cfg(feature = "std")]
 pub fn yn() -> StdQuestionBuilder<bool> {
-    StdQuestionBuilder::from(|s: &str| match s.to_lowercase().as_str() {
+    StdQuestionBuilder::from(parse_yn)
+}
+
+/// Parses `input` as in the [`yn`] pattern.
+///
+/// [`yn`]: fn.yn.html
+pub(crate) fn parse_yn(input: &str) -> Result<bool, std::str::ParseBoolError> {
+    match input.to_lowercase().as_str() {
         "true" | "t" | "yes" | "y" => Ok(true),
         "false" | "f" | "no" | "n" => Ok(false),
-        _ => s.parse(),
-    })
+        _ => input.parse(),
+    }
+}
+
+/// Parses `input` with the answers of `locale`, after making lowercase and removing accents.
+pub(crate) fn parse_yn_locale(
+    input: &str,
+    locale: Locale,
+) -> Result<bool, std::str::ParseBoolError> {
+    let normalized = fold_accents(&input.to_lowercase());
+    let (affirmative, negative) = locale.tokens();
+    if affirmative.contains(&normalized.as_str()) {
+        Ok(true)
+    } else if negative.contains(&normalized.as_str()) {
+        Ok(false)
+    } else {
+        input.parse()
+    }
+}
+
+/// Replaces accented latin letters by the letter without the accent.
+fn fold_accents(input: &str) -> String {
+    input
+        .chars()
+        .map(|c| match c {
+            'á' | 'à' | 'â' | 'ä' | 'ã' => 'a',
+            'é' | 'è' | 'ê' | 'ë' => 'e',
+            'í' | 'ì' | 'î' | 'ï' => 'i',
+            'ó' | 'ò' | 'ô' | 'ö' | 'õ' => 'o',
+            'ú' | 'ù' | 'û' | 'ü' => 'u',
+            'ç' => 'c',
+            'ñ' => 'n',
+            c => c,
+        })
+        .collect()
 }
 
 /// Test if the value is inside an iterator
@@ -33,6 +115,10 @@ pub fn yn() -> StdQuestionBuilder<bool> {
 /// # Remarks
 ///
 /// To prevent infinite loops, make sure `iterator` is finite.
+/// Check out [`select_listed`] to also accept unambiguous prefixes of the options.
+///
+/// [`select_listed`]: fn.select_listed.html
+#[cfg(feature = "std")]
 pub fn select<T, I>(iterator: I) -> StdQuestionBuilder<T>
 where
     T: PartialEq + FromStr + Send + Sync + 'static,
@@ -47,6 +133,7 @@ where
 /// # Remarks
 ///
 /// To prevent infinite loops, make sure `iterator` is finite.
+#[cfg(feature = "std")]
 pub fn select_with_msg<T, I, M>(iterator: I, message: M) -> StdQuestionBuilder<T>
 where
     T: PartialEq + FromStr + Send + Sync + 'static,
@@ -62,14 +149,602 @@ where
     )
 }
 
+/// Test if the value is inside an iterator, keeping the options as given by `ToString`.
+///
+/// Unlike [`select`], the question knows its options, so [`allow_prefix`] accepts
+/// unambiguous prefixes of them and [`describe`] lists them.
+///
+/// # Examples
+///
+/// ```
+/// let command = asking::select_listed(vec![String::from("commit"), String::from("checkout")])
+///     .allow_prefix()
+///     .reader("com\n".as_bytes())
+///     .ask_and_wait()
+///     .unwrap();
+/// assert_eq!(command, "commit");
+/// ```
+///
+/// [`select`]: fn.select.html
+/// [`allow_prefix`]: struct.QuestionBuilder.html#method.allow_prefix
+/// [`describe`]: struct.QuestionBuilder.html#method.describe
+#[cfg(feature = "std")]
+pub fn select_listed<T, I>(iterator: I) -> StdQuestionBuilder<T>
+where
+    T: PartialEq + ToString + FromStr + Send + Sync + 'static,
+    <T as FromStr>::Err: Send + Sync + Error + 'static,
+    I: IntoIterator<Item = T>,
+{
+    let options: Vec<T> = iterator.into_iter().collect();
+    let labels = options.iter().map(ToString::to_string).collect();
+    select(options).options(labels)
+}
+
+/// Choose one option by its label, while returning its value.
+///
+/// The default message lists the labels, numbered from `1`.
+/// The input can be the label, the value (as given by `ToString`) or the number of an option.
+///
+/// # Examples
+///
+/// ```no_run
+/// let _country = asking::select_labeled(vec![("United States", "US"), ("Chile", "CL")])
+///     .ask_and_wait();
+/// ```
+#[cfg(feature = "std")]
+pub fn select_labeled<L, T, I>(pairs: I) -> StdQuestionBuilder<T>
+where
+    L: ToString,
+    T: ToString + Clone + Send + Sync + 'static,
+    I: IntoIterator<Item = (L, T)>,
+{
+    let options: Vec<(String, T)> = pairs
+        .into_iter()
+        .map(|(label, value)| (label.to_string(), value))
+        .collect();
+    let menu: String = options
+        .iter()
+        .enumerate()
+        .map(|(index, (label, _))| format!("{}) {}\n", index + 1, label))
+        .collect();
+    let labels = options.iter().map(|(label, _)| label.clone()).collect();
+    StdQuestionBuilder::from(move |s: &str| parse_labeled(s, &options))
+        .parser_feedback_toggle()
+        .message(menu)
+        .options(labels)
+}
+
+/// Finds the value of the option that `input` refers to, by label, value or number.
+#[cfg_attr(not(feature = "std"), allow(dead_code))]
+pub(crate) fn parse_labeled<T>(input: &str, options: &[(String, T)]) -> Result<T, SelectError>
+where
+    T: ToString + Clone,
+{
+    options
+        .iter()
+        .find(|(label, _)| label == input)
+        .or_else(|| options.iter().find(|(_, value)| value.to_string() == input))
+        .or_else(|| {
+            input
+                .parse::<usize>()
+                .ok()
+                .and_then(|number| options.get(number.checked_sub(1)?))
+        })
+        .map(|(_, value)| value.clone())
+        .ok_or_else(|| SelectError::Unknown {
+            input: input.to_string(),
+        })
+}
+
+/// Choose a value by typing its keyword, like in command-style prompts.
+///
+/// Values can be of any type, they do not need to implement `FromStr`.
+/// Unknown keywords are rejected with an error that lists all keywords.
+///
+/// # Examples
+///
+/// ```no_run
+/// #[derive(Clone)]
+/// enum Command {
+///     Start,
+///     Stop,
+///     Restart,
+/// }
+///
+/// let _command = asking::select_map(vec![
+///     ("start", Command::Start),
+///     ("stop", Command::Stop),
+///     ("restart", Command::Restart),
+/// ])
+/// .message("Command (start/stop/restart): ")
+/// .ask_and_wait();
+/// ```
+#[cfg(feature = "std")]
+pub fn select_map<K, T, I>(pairs: I) -> StdQuestionBuilder<T>
+where
+    K: ToString,
+    T: Clone + Send + Sync + 'static,
+    I: IntoIterator<Item = (K, T)>,
+{
+    let options: Vec<(String, T)> = pairs
+        .into_iter()
+        .map(|(keyword, value)| (keyword.to_string(), value))
+        .collect();
+    let keywords = options.iter().map(|(keyword, _)| keyword.clone()).collect();
+    StdQuestionBuilder::from(move |s: &str| parse_keyword(s, &options))
+        .parser_feedback_toggle()
+        .options(keywords)
+}
+
+/// Finds the value of the option whose keyword is `input`.
+#[cfg_attr(not(feature = "std"), allow(dead_code))]
+pub(crate) fn parse_keyword<T>(input: &str, options: &[(String, T)]) -> Result<T, SelectError>
+where
+    T: Clone,
+{
+    options
+        .iter()
+        .find(|(keyword, _)| keyword == input)
+        .map(|(_, value)| value.clone())
+        .ok_or_else(|| SelectError::UnknownKeyword {
+            input: input.to_string(),
+            keywords: options.iter().map(|(keyword, _)| keyword.clone()).collect(),
+        })
+}
+
+/// Choose one option, ignoring case and whitespace, while returning the option as given.
+///
+/// The input and the options (as given by `ToString`) are compared after normalizing them:
+/// 1. Remove leading and trailing whitespace.
+/// 2. Replace every run of whitespace inside by a single space.
+/// 3. Make lowercase, as [`str::to_lowercase`] does.
+///
+/// The first option that matches is returned.
+///
+/// # Examples
+///
+/// ```
+/// let fruit = asking::select_normalized(vec!["Apple", "Passion Fruit"])
+///     .reader("  passion   FRUIT \n".as_bytes())
+///     .ask_and_wait()
+///     .unwrap();
+/// assert_eq!(fruit, "Passion Fruit");
+/// ```
+///
+/// [`str::to_lowercase`]: https://doc.rust-lang.org/std/primitive.str.html#method.to_lowercase
+#[cfg(feature = "std")]
+pub fn select_normalized<T, I>(iterator: I) -> StdQuestionBuilder<T>
+where
+    T: ToString + Clone + Send + Sync + 'static,
+    I: IntoIterator<Item = T>,
+{
+    let options: Vec<(String, T)> = iterator
+        .into_iter()
+        .map(|option| (normalize(&option.to_string()), option))
+        .collect();
+    let labels = options
+        .iter()
+        .map(|(_, option)| option.to_string())
+        .collect();
+    StdQuestionBuilder::from(move |s: &str| parse_normalized(s, &options))
+        .parser_feedback_toggle()
+        .options(labels)
+}
+
+/// Finds the first option whose normalized form is that of `input`.
+#[cfg_attr(not(feature = "std"), allow(dead_code))]
+pub(crate) fn parse_normalized<T>(input: &str, options: &[(String, T)]) -> Result<T, SelectError>
+where
+    T: Clone,
+{
+    let normalized = normalize(input);
+    options
+        .iter()
+        .find(|(option, _)| *option == normalized)
+        .map(|(_, value)| value.clone())
+        .ok_or_else(|| SelectError::Unknown {
+            input: input.trim().to_string(),
+        })
+}
+
+/// Trims, collapses inner whitespace into single spaces, and makes lowercase.
+fn normalize(text: &str) -> String {
+    text.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+/// Finds the option that `input` refers to, either exactly or as a prefix, ignoring case.
+///
+/// Returns `None` if no option matches.
+pub(crate) fn resolve_prefix<'a>(
+    input: &str,
+    options: &'a [String],
+) -> Result<Option<&'a String>, SelectError> {
+    if let Some(option) = options.iter().find(|option| option.as_str() == input) {
+        return Ok(Some(option));
+    }
+    let lowercase = input.to_lowercase();
+    let candidates: Vec<&String> = options
+        .iter()
+        .filter(|option| option.to_lowercase().starts_with(&lowercase))
+        .collect();
+    match candidates.as_slice() {
+        [] => Ok(None),
+        [option] => Ok(Some(option)),
+        _ => Err(SelectError::Ambiguous {
+            input: input.to_string(),
+            candidates: candidates.into_iter().cloned().collect(),
+        }),
+    }
+}
+
+/// Choose one variant of an enum.
+///
+/// All variants are listed in the help message.
+///
+/// # Remarks
+///
+/// Requires the `strum` feature.
+#[cfg(all(feature = "std", feature = "strum"))]
+pub fn select_enum<E>() -> StdQuestionBuilder<E>
+where
+    E: strum::IntoEnumIterator + PartialEq + ToString + FromStr + Send + Sync + 'static,
+    <E as FromStr>::Err: Send + Sync + Error + 'static,
+{
+    let names: Vec<String> = E::iter().map(|variant| variant.to_string()).collect();
+    select_listed(E::iter()).repeat_help(format!("Options: {}.\n", names.join(", ")))
+}
+
 /// Date question.
 ///
 /// `NaiveDate` parses the `%Y-%m-%d` format.
+#[cfg(feature = "std")]
 pub fn date() -> StdQuestionBuilder<NaiveDate> {
     StdQuestionBuilder::default()
 }
 
 /// Text question.
+#[cfg(feature = "std")]
 pub fn text() -> StdQuestionBuilder<String> {
     StdQuestionBuilder::default()
 }
+
+/// Password question.
+///
+/// The input is [`mask`]ed and, by default, it is an error to ask it
+/// outside a terminal (check out [`require_tty_for_mask`]).
+///
+/// [`mask`]: struct.QuestionBuilder.html#method.mask
+/// [`require_tty_for_mask`]: struct.QuestionBuilder.html#method.require_tty_for_mask
+#[cfg(feature = "std")]
+pub fn password() -> StdQuestionBuilder<String> {
+    text().mask().require_tty_for_mask()
+}
+
+/// Single line text question.
+///
+/// The default parser rejects input containing control characters, like tabs,
+/// and input that is empty or contains only whitespace.
+/// Check out [`text_line_rules`] to change which input is rejected.
+///
+/// # Remarks
+///
+/// The default [`preparser`] trims the end of the input, so the trailing newline is not checked.
+///
+/// [`text_line_rules`]: struct.QuestionBuilder.html#method.text_line_rules
+/// [`preparser`]: struct.QuestionBuilder.html#method.preparser
+#[cfg(feature = "std")]
+pub fn text_line() -> StdQuestionBuilder<String> {
+    StdQuestionBuilder::from(|s: &str| parse_text_line(s, true, true))
+}
+
+/// Question for text that is not blank, as a [`NonEmptyString`].
+///
+/// The input is trimmed, and empty or whitespace-only input is rejected displaying the error.
+///
+/// [`NonEmptyString`]: struct.NonEmptyString.html
+#[cfg(feature = "std")]
+pub fn nonempty_text() -> StdQuestionBuilder<NonEmptyString> {
+    StdQuestionBuilder::default().parser_feedback_toggle()
+}
+
+/// Pause until the user presses Enter.
+///
+/// The default message is "Press Enter to continue...", and any input is accepted.
+///
+/// # Examples
+///
+/// ```no_run
+/// asking::pause().ask_and_wait().unwrap();
+/// ```
+#[cfg(feature = "std")]
+pub fn pause() -> StdQuestionBuilder<()> {
+    StdQuestionBuilder::from(|_: &str| Ok::<(), std::convert::Infallible>(()))
+        .message("Press Enter to continue...")
+}
+
+/// URL question.
+///
+/// The default parser requires an absolute URL, starting with a scheme like `https://`,
+/// and its errors are displayed. For example, `example.com` is rejected.
+///
+/// # Remarks
+///
+/// The URL is returned as given, so you can parse it further with your URL type of choice.
+#[cfg(feature = "std")]
+pub fn url() -> StdQuestionBuilder<String> {
+    StdQuestionBuilder::from(parse_url)
+        .parser_feedback_toggle()
+        .help("Use a full URL, including its scheme, like https://example.com.\n")
+}
+
+/// Checks that `input` is an absolute URL, as in the [`url`] pattern.
+///
+/// [`url`]: fn.url.html
+#[cfg_attr(not(feature = "std"), allow(dead_code))]
+pub(crate) fn parse_url(input: &str) -> Result<String, UrlError> {
+    if input.chars().any(char::is_whitespace) {
+        return Err(UrlError::Whitespace);
+    }
+    let (scheme, rest) = input
+        .split_once(':')
+        .ok_or_else(|| UrlError::MissingScheme {
+            input: input.to_string(),
+        })?;
+    let mut characters = scheme.chars();
+    let valid_scheme = characters.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && characters.all(|c| c.is_ascii_alphanumeric() || "+-.".contains(c));
+    if !valid_scheme {
+        return Err(UrlError::InvalidScheme {
+            scheme: scheme.to_string(),
+        });
+    }
+    if let Some(authority) = rest.strip_prefix("//") {
+        if authority.split(&['/', '?', '#'][..]).next() == Some("") {
+            return Err(UrlError::MissingHost);
+        }
+    }
+    Ok(input.to_string())
+}
+
+/// Key-value question.
+///
+/// The default parser reads entries separated by spaces, each of the form `key=value`.
+/// For example, `a=1 b=2 c=3`. Check out [`kv_separators`] to change the separators.
+///
+/// # Remarks
+///
+/// Entries without a separator between key and value, as well as duplicated keys, are rejected.
+///
+/// [`kv_separators`]: struct.QuestionBuilder.html#method.kv_separators
+#[cfg(feature = "std")]
+pub fn key_values() -> StdQuestionBuilder<HashMap<String, String>> {
+    StdQuestionBuilder::from(|s: &str| parse_key_values(s, "=", " "))
+}
+
+/// Parses `input` into a map, splitting entries by `entry_separator`
+/// and keys from values by `pair_separator`.
+///
+/// Empty entries are skipped.
+pub(crate) fn parse_key_values(
+    input: &str,
+    pair_separator: &str,
+    entry_separator: &str,
+) -> Result<HashMap<String, String>, KeyValueError> {
+    let mut map = HashMap::new();
+    for entry in input.split(entry_separator).map(str::trim) {
+        if entry.is_empty() {
+            continue;
+        }
+        let (key, value) =
+            entry
+                .split_once(pair_separator)
+                .ok_or_else(|| KeyValueError::MissingSeparator {
+                    entry: entry.to_string(),
+                    separator: pair_separator.to_string(),
+                })?;
+        if map
+            .insert(key.trim().to_string(), value.trim().to_string())
+            .is_some()
+        {
+            return Err(KeyValueError::DuplicateKey {
+                key: key.trim().to_string(),
+            });
+        }
+    }
+    Ok(map)
+}
+
+/// Parses `input` as a line of text,
+/// rejecting control characters and blank input as specified.
+pub(crate) fn parse_text_line(
+    input: &str,
+    reject_control_chars: bool,
+    reject_blank: bool,
+) -> Result<String, TextLineError> {
+    if reject_control_chars && input.chars().any(char::is_control) {
+        return Err(TextLineError::ControlCharacter);
+    }
+    if reject_blank && input.trim().is_empty() {
+        return Err(TextLineError::Blank);
+    }
+    Ok(input.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NonEmptyString;
+
+    #[test]
+    fn key_values() {
+        let map = parse_key_values("a=1 b=2  c=3", "=", " ").unwrap();
+        assert_eq!(map.len(), 3);
+        assert_eq!(map["a"], "1");
+        assert_eq!(map["b"], "2");
+        assert_eq!(map["c"], "3");
+    }
+
+    #[test]
+    fn key_values_malformed() {
+        assert_eq!(
+            parse_key_values("a=1 b", "=", " "),
+            Err(KeyValueError::MissingSeparator {
+                entry: "b".to_string(),
+                separator: "=".to_string()
+            })
+        );
+        assert_eq!(
+            parse_key_values("a=1 a=2", "=", " "),
+            Err(KeyValueError::DuplicateKey {
+                key: "a".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn text_line() {
+        assert_eq!(parse_text_line("a b", true, true), Ok("a b".to_string()));
+        assert_eq!(
+            parse_text_line("a\tb", true, true),
+            Err(TextLineError::ControlCharacter)
+        );
+        assert_eq!(parse_text_line("a\tb", false, true), Ok("a\tb".to_string()));
+        assert_eq!(parse_text_line("  ", true, true), Err(TextLineError::Blank));
+        assert_eq!(parse_text_line("  ", true, false), Ok("  ".to_string()));
+    }
+
+    #[test]
+    fn resolve_prefix() {
+        let options = vec!["commit".to_string(), "checkout".to_string()];
+        assert_eq!(
+            super::resolve_prefix("c", &options),
+            Err(SelectError::Ambiguous {
+                input: "c".to_string(),
+                candidates: options.clone()
+            })
+        );
+        assert_eq!(
+            super::resolve_prefix("COM", &options),
+            Ok(Some(&options[0]))
+        );
+        assert_eq!(super::resolve_prefix("push", &options), Ok(None));
+    }
+
+    #[cfg(feature = "strum")]
+    #[test]
+    fn select_enum() {
+        #[derive(Debug, PartialEq, strum::EnumIter, strum::EnumString, strum::Display)]
+        enum Color {
+            Red,
+            Green,
+        }
+
+        let answer = super::select_enum::<Color>()
+            .reader("Blue\nGreen\n".as_bytes())
+            .writer(Vec::new())
+            .ask_and_wait()
+            .unwrap();
+        assert_eq!(answer, Color::Green);
+    }
+
+    #[test]
+    fn labeled() {
+        let options = vec![
+            ("United States".to_string(), "US"),
+            ("Chile".to_string(), "CL"),
+        ];
+        assert_eq!(parse_labeled("United States", &options), Ok("US"));
+        assert_eq!(parse_labeled("CL", &options), Ok("CL"));
+        assert_eq!(parse_labeled("1", &options), Ok("US"));
+        assert_eq!(
+            parse_labeled("0", &options),
+            Err(SelectError::Unknown {
+                input: "0".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn keyword() {
+        let options = vec![("start".to_string(), 1), ("stop".to_string(), 2)];
+        assert_eq!(parse_keyword("stop", &options), Ok(2));
+        assert_eq!(
+            parse_keyword("pause", &options),
+            Err(SelectError::UnknownKeyword {
+                input: "pause".to_string(),
+                keywords: vec!["start".to_string(), "stop".to_string()],
+            })
+        );
+    }
+
+    #[test]
+    fn normalized() {
+        let options = vec![
+            (normalize("Apple"), "Apple"),
+            (normalize("Passion  Fruit"), "Passion  Fruit"),
+        ];
+        assert_eq!(parse_normalized("  aPPle \t", &options), Ok("Apple"));
+        assert_eq!(
+            parse_normalized("passion fruit", &options),
+            Ok("Passion  Fruit")
+        );
+        assert_eq!(
+            parse_normalized(" apples ", &options),
+            Err(SelectError::Unknown {
+                input: "apples".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn url() {
+        assert_eq!(
+            parse_url("https://example.com"),
+            Ok("https://example.com".to_string())
+        );
+        assert_eq!(
+            parse_url("mailto:someone@example.com"),
+            Ok("mailto:someone@example.com".to_string())
+        );
+        assert_eq!(
+            parse_url("example.com"),
+            Err(UrlError::MissingScheme {
+                input: "example.com".to_string()
+            })
+        );
+        assert_eq!(
+            parse_url("1http://example.com"),
+            Err(UrlError::InvalidScheme {
+                scheme: "1http".to_string()
+            })
+        );
+        assert_eq!(parse_url("https://"), Err(UrlError::MissingHost));
+        assert_eq!(
+            parse_url("https://example.com/a b"),
+            Err(UrlError::Whitespace)
+        );
+    }
+
+    #[test]
+    fn yn_locale() {
+        assert_eq!(parse_yn_locale("Ja", Locale::German), Ok(true));
+        assert_eq!(parse_yn_locale("NEIN", Locale::German), Ok(false));
+        assert_eq!(parse_yn_locale("Sí", Locale::Spanish), Ok(true));
+        assert_eq!(parse_yn_locale("si", Locale::Spanish), Ok(true));
+        assert_eq!(parse_yn_locale("Não", Locale::Portuguese), Ok(false));
+        assert_eq!(parse_yn_locale("oui", Locale::French), Ok(true));
+        assert_eq!(parse_yn_locale("true", Locale::German), Ok(true));
+        assert!(parse_yn_locale("yes", Locale::German).is_err());
+    }
+
+    #[test]
+    fn nonempty_string() {
+        let text: NonEmptyString = "  hi  ".parse().unwrap();
+        assert_eq!(text.as_str(), "hi");
+        assert_eq!("   ".parse::<NonEmptyString>(), Err(TextLineError::Blank));
+        assert_eq!("".parse::<NonEmptyString>(), Err(TextLineError::Blank));
+    }
+}
@@ -0,0 +1,176 @@
+use async_std::{
+    io::{Read, Write},
+    task::{Context, Poll},
+};
+use std::{
+    pin::Pin,
+    sync::{Arc, Mutex},
+};
+
+type Prompt = Arc<dyn Fn(&str) -> Option<String> + Send + Sync>;
+type Display = Arc<dyn Fn(&str) + Send + Sync>;
+
+/// Constructs a reader and writer that ask for input through a blocking `prompt` function.
+///
+/// Useful where there is no standard input or output, for example, in the browser,
+/// where `prompt` can call `window.prompt` and `display` can call `console.log`.
+///
+/// Output is held until input is needed, and then it is passed to `prompt` as its message.
+/// If `prompt` returns `None`, like a cancelled `window.prompt`, the reader gets to the end of file.
+/// Output that is left when the writer is dropped, like the feedback, is passed to `display`.
+///
+/// # Examples
+///
+/// ```
+/// # async_std::task::block_on(async {
+/// let (reader, writer) = asking::prompt_io(
+///     |message| {
+///         assert_eq!(message, "Continue? ");
+///         Some("yes".to_string())
+///     },
+///     |output| assert_eq!(output, "Super!"),
+/// );
+/// let answer = asking::yn()
+///     .message("Continue? ")
+///     .feedback(|_| "Super!".to_string())
+///     .reader(reader)
+///     .writer(writer)
+///     .ask()
+///     .await
+///     .unwrap();
+/// assert!(answer);
+/// # });
+/// ```
+///
+/// In the browser, with the `web-sys` crate, it could look like the following.
+///
+/// ```ignore
+/// let window = web_sys::window().unwrap();
+/// let (reader, writer) = asking::prompt_io(
+///     move |message| window.prompt_with_message(message).ok().flatten(),
+///     |output| web_sys::console::log_1(&output.into()),
+/// );
+/// ```
+pub fn prompt_io<F, G>(prompt: F, display: G) -> (PromptReader, PromptWriter)
+where
+    F: Fn(&str) -> Option<String> + Send + Sync + 'static,
+    G: Fn(&str) + Send + Sync + 'static,
+{
+    let pending = Arc::new(Mutex::new(Vec::new()));
+    let reader = PromptReader {
+        prompt: Arc::new(prompt),
+        pending: pending.clone(),
+        line: Vec::new(),
+        position: 0,
+    };
+    let writer = PromptWriter {
+        display: Arc::new(display),
+        pending,
+    };
+    (reader, writer)
+}
+
+/// Reader that asks for each line of input through a prompt function.
+///
+/// Constructed by [`prompt_io`].
+///
+/// [`prompt_io`]: fn.prompt_io.html
+pub struct PromptReader {
+    prompt: Prompt,
+    pending: Arc<Mutex<Vec<u8>>>,
+    line: Vec<u8>,
+    position: usize,
+}
+
+impl std::fmt::Debug for PromptReader {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PromptReader")
+            .field("line", &self.line)
+            .field("position", &self.position)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Read for PromptReader {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        _: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<std::io::Result<usize>> {
+        if self.position == self.line.len() {
+            let message = take_pending(&self.pending);
+            match (self.prompt)(&message) {
+                None => return Poll::Ready(Ok(0)),
+                Some(mut line) => {
+                    if !line.ends_with('\n') {
+                        line.push('\n');
+                    }
+                    self.line = line.into_bytes();
+                    self.position = 0;
+                }
+            }
+        }
+        let this = &mut *self;
+        let n = buf.len().min(this.line.len() - this.position);
+        buf[..n].copy_from_slice(&this.line[this.position..this.position + n]);
+        this.position += n;
+        Poll::Ready(Ok(n))
+    }
+}
+
+/// Writer that holds output until the matching [`PromptReader`] asks for input.
+///
+/// Constructed by [`prompt_io`].
+///
+/// [`PromptReader`]: struct.PromptReader.html
+/// [`prompt_io`]: fn.prompt_io.html
+pub struct PromptWriter {
+    display: Display,
+    pending: Arc<Mutex<Vec<u8>>>,
+}
+
+impl std::fmt::Debug for PromptWriter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PromptWriter")
+            .field("pending", &self.pending)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Write for PromptWriter {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        _: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<std::io::Result<usize>> {
+        match self.pending.lock() {
+            Ok(mut pending) => pending.extend_from_slice(buf),
+            Err(_) => return Poll::Ready(Err(std::io::ErrorKind::BrokenPipe.into())),
+        }
+        Poll::Ready(Ok(buf.len()))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        self.poll_flush(cx)
+    }
+}
+
+impl Drop for PromptWriter {
+    fn drop(&mut self) {
+        let output = take_pending(&self.pending);
+        if !output.is_empty() {
+            (self.display)(&output);
+        }
+    }
+}
+
+fn take_pending(pending: &Mutex<Vec<u8>>) -> String {
+    match pending.lock() {
+        Ok(mut pending) => String::from_utf8_lossy(&std::mem::take(&mut *pending)).into_owned(),
+        Err(_) => String::new(),
+    }
+}
@@ -1,20 +1,79 @@
 use async_std::{
-    io::{
-        prelude::{BufReadExt, WriteExt},
-        BufReader, BufWriter, Read, Write,
-    },
+    io::{prelude::WriteExt, BufRead, BufReader, BufWriter, Cursor, Read, Write},
     sync::Arc,
+    task::Poll,
+};
+use core::{fmt::Debug, ops::RangeBounds, str::FromStr};
+use eyre::{Report, WrapErr};
+use std::{
+    collections::HashMap,
+    error::Error,
+    future::Future,
+    io::IsTerminal,
+    marker::Unpin,
+    path::PathBuf,
+    pin::Pin,
+    string::ToString,
+    time::{Duration, Instant},
 };
-use core::{fmt::Debug, str::FromStr};
-use eyre::Report;
-use std::{error::Error, marker::Unpin, string::ToString, time::Duration};
 
-use crate::error::ProcessingError;
+use crate::{
+    error::{ConfigError, ProcessingError},
+    Context,
+};
 
+mod cancellation;
+mod config;
+mod default_source;
+mod descriptor;
+mod encoding;
 mod executor;
+mod integer;
+mod line_editor;
+mod locale;
+mod parts;
+mod progress;
+mod retry_policy;
+#[cfg(feature = "std")]
 mod standard;
+mod terminal;
+mod test_mode;
+pub use cancellation::Cancellation;
+pub use config::QuestionConfig;
+pub use default_source::DefaultSource;
+pub use descriptor::PromptDescriptor;
+pub use encoding::Encoding;
 pub use executor::Executor;
+pub use integer::Integer;
+pub use locale::Locale;
+pub use parts::QuestionParts;
+pub use progress::Progress;
+pub use retry_policy::RetryPolicy;
+#[cfg(feature = "std")]
 pub use standard::StdQuestionBuilder;
+pub use test_mode::TestMode;
+
+type StrTest = Arc<dyn Fn(&str) -> eyre::Result<()> + Send + Sync>;
+type Parser<T> = Arc<dyn Fn(&str) -> eyre::Result<T> + Send + Sync>;
+type Test<T> = Arc<dyn Fn(&T) -> eyre::Result<()> + Send + Sync>;
+type AttemptsFeedback = Arc<dyn Fn(usize) -> String + Send + Sync>;
+type Transform = Arc<dyn Fn(String) -> Pin<Box<dyn Future<Output = String> + Send>> + Send + Sync>;
+type Preparser = Arc<dyn Fn(String) -> String + Send + Sync>;
+type Message = Arc<dyn Fn(usize) -> String + Send + Sync>;
+type Feedback<T> = Arc<dyn Fn(&T) -> String + Send + Sync>;
+type Cancel<T> = Arc<dyn Fn(&T) -> Option<T> + Send + Sync>;
+type Predicate<T> = Arc<dyn Fn(&T) -> bool + Send + Sync>;
+type Template<T> = Arc<dyn Fn(&T) -> Option<String> + Send + Sync>;
+type Preview = Arc<dyn Fn(&str) -> String + Send + Sync>;
+type Round<T> = Arc<dyn Fn(T) -> T + Send + Sync>;
+type AsyncTest<T> = Arc<
+    dyn Fn(&T, Progress) -> Pin<Box<dyn Future<Output = eyre::Result<()>> + Send>> + Send + Sync,
+>;
+type Notify = Arc<dyn Fn(&str) + Send + Sync>;
+type DefaultChain<T> =
+    Arc<dyn Fn(Parser<T>) -> Pin<Box<dyn Future<Output = Option<T>> + Send>> + Send + Sync>;
+type TimedFeedback<T> = Arc<dyn Fn(&T, Duration) -> String + Send + Sync>;
+type OutcomeLog<T> = (Box<dyn Write + Unpin + Send + Sync>, Feedback<T>);
 
 /// Async I/O handler (in builder form).
 ///
@@ -25,9 +84,19 @@ pub use standard::StdQuestionBuilder;
 /// + [Message](#message)
 /// + [Testing value](#testing-value)
 /// + [Testing value extended](#testing-value-extended)
+/// + [Numeric value](#numeric-value)
+/// + [Floating-point value](#floating-point-value)
+/// + [Unsigned integer value](#unsigned-integer-value)
 /// + [Useful Settings](#useful-settings)
+/// + [Terminal](#terminal)
 /// + [Prompt functionalities](#prompt-functionalities)
 /// + [Processing Text Input](#processing-text-input)
+/// + [Yes/No input](#yesno-input)
+/// + [Text-line input](#text-line-input)
+/// + [Text value](#text-value)
+/// + [Options](#options)
+/// + [Key-value input](#key-value-input)
+/// + [Questionnaire](#questionnaire)
 /// + [Advanced Methods](#advanced-methods)
 ///
 /// # Processing
@@ -48,18 +117,26 @@ pub use standard::StdQuestionBuilder;
 pub struct QuestionBuilder<T, R, W> {
     reader: BufReader<R>,
     writer: BufWriter<W>,
-    message: (String, bool),
-    help: (String, bool),
+    config: QuestionConfig,
+    attempt: usize,
+    inputs_read: usize,
+    session_used: usize,
+    input_time: Duration,
+    asked_at: Instant,
+    once: bool,
     default: Option<T>,
-    feedback: Arc<dyn Fn(&T) -> String + Send + Sync>,
-    preparser: Arc<dyn Fn(String) -> String + Send + Sync>,
-    str_tests: Vec<(Arc<dyn Fn(&str) -> eyre::Result<()> + Send + Sync>, bool)>,
-    parser: (Arc<dyn Fn(&str) -> eyre::Result<T> + Send + Sync>, bool),
-    tests: Vec<(Arc<dyn Fn(&T) -> eyre::Result<()> + Send + Sync>, bool)>,
-    error_formatter: Arc<dyn Fn(String) -> String + Send + Sync>,
-    executor: Executor,
-    attempts: Option<(usize, Arc<dyn Fn(usize) -> String + Send + Sync>)>,
-    required: (String, bool),
+    default_chain: Option<DefaultChain<T>>,
+    feedback: Feedback<T>,
+    timed_feedback: Option<TimedFeedback<T>>,
+    parser: (Parser<T>, bool),
+    tests: Vec<(Test<T>, bool)>,
+    double_confirm: Option<(String, Cancel<T>)>,
+    confirm_value: Option<Template<T>>,
+    reset_attempts_on: Option<Predicate<T>>,
+    accept_if: Option<Predicate<T>>,
+    round: Option<Round<T>>,
+    outcome_log: Option<OutcomeLog<T>>,
+    async_tests: Vec<AsyncTest<T>>,
 }
 
 /// # Constructor
@@ -87,21 +164,29 @@ where
         Self {
             reader: BufReader::new(reader),
             writer: BufWriter::new(writer),
-            message: (String::default(), bool::default()),
-            help: (String::default(), bool::default()),
+            config: QuestionConfig::default(),
+            attempt: 0,
+            inputs_read: 0,
+            session_used: 0,
+            input_time: Duration::default(),
+            asked_at: Instant::now(),
+            once: false,
             default: None,
+            default_chain: None,
             feedback: Arc::new(|_| String::default()),
-            preparser: Arc::new(|s| s.trim_end().to_string()),
-            str_tests: Vec::default(),
+            timed_feedback: None,
             parser: (
                 Arc::new(move |s| parser(s).map_err(|e| Report::new(e))),
                 bool::default(),
             ),
             tests: Vec::default(),
-            error_formatter: Arc::new(|s| s + "\n"),
-            executor: Executor::None,
-            attempts: None,
-            required: (String::default(), bool::default()),
+            double_confirm: None,
+            confirm_value: None,
+            reset_attempts_on: None,
+            accept_if: None,
+            round: None,
+            outcome_log: None,
+            async_tests: Vec::default(),
         }
     }
 }
@@ -122,38 +207,338 @@ where
     }
 }
 
+impl<T, S> QuestionBuilder<T, S, S>
+where
+    T: FromStr,
+    <T as FromStr>::Err: Error + Send + Sync + 'static,
+    S: Read + Write + Clone,
+{
+    /// Constructs a new `QuestionBuilder<T, S, S>` that reads from and writes to
+    /// the same bidirectional `stream`, like an `async_std::net::TcpStream`.
+    ///
+    /// The stream is cloned, so both halves refer to the same connection.
+    /// The [`parser`] is given by the implementation of `FromStr`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # async_std::task::block_on(async {
+    /// use async_std::net::TcpStream;
+    ///
+    /// let stream = TcpStream::connect("127.0.0.1:8080").await?;
+    /// let name: String = asking::QuestionBuilder::duplex(stream)
+    ///     .message("Name? ")
+    ///     .ask()
+    ///     .await?;
+    /// # Ok::<(), eyre::Report>(())
+    /// # });
+    /// ```
+    ///
+    /// [`parser`]: #method.parser
+    pub fn duplex(stream: S) -> Self {
+        Self::new_fromstr(stream.clone(), stream)
+    }
+}
+
 /// # Message
 ///
 /// Main messages that will be displayed.
 impl<T, R, W> QuestionBuilder<T, R, W> {
     /// Message to be displayed.
     pub fn message(mut self, message: impl ToString) -> Self {
-        self.message = (message.to_string(), false);
+        let message = message.to_string();
+        self.config.message = Arc::new(move |_| message.clone());
+        self.config.repeat_message = false;
         self
     }
     /// Message to be displayed repeatedly before each attempt.
     pub fn repeat_message(mut self, message: impl ToString) -> Self {
-        self.message = (message.to_string(), true);
+        let message = message.to_string();
+        self.config.message = Arc::new(move |_| message.clone());
+        self.config.repeat_message = true;
+        self
+    }
+    /// Message to be displayed before each attempt, depending on the attempt number.
+    ///
+    /// Attempts are numbered starting from `1`, whether they are bounded or not.
+    pub fn message_fn<F>(mut self, message: F) -> Self
+    where
+        F: Fn(usize) -> String + Send + Sync + 'static,
+    {
+        self.config.message = Arc::new(message);
+        self.config.repeat_message = true;
+        self
+    }
+    /// Display the message only if input is read.
+    ///
+    /// For example, with [`dry_run`], the default value is returned without displaying anything,
+    /// which avoids noisy prompts in fully scripted runs.
+    ///
+    /// [`dry_run`]: #method.dry_run
+    pub fn message_lazy(mut self) -> Self {
+        self.config.message_lazy = true;
         self
     }
     /// Help message to be displayed after the first failed attempt.
     pub fn help(mut self, help: impl ToString) -> Self {
-        self.help = (help.to_string(), false);
+        self.config.help = help.to_string();
+        self.config.repeat_help = false;
         self
     }
     /// Help message to be displayed every time an attempt failed.
     pub fn repeat_help(mut self, help: impl ToString) -> Self {
-        self.help = (help.to_string(), true);
+        self.config.help = help.to_string();
+        self.config.repeat_help = true;
+        self
+    }
+    /// Display the help only after `n` failed attempts, that is, from failure `n + 1` on.
+    ///
+    /// This avoids nagging the user on the first mistake.
+    /// The default value is `0`, displaying the help from the first failure.
+    ///
+    /// # Remarks
+    ///
+    /// The [`help`] is displayed once, and the [`repeat_help`] after every later failure.
+    ///
+    /// [`help`]: #method.help
+    /// [`repeat_help`]: #method.repeat_help
+    pub fn help_after(mut self, n: usize) -> Self {
+        self.config.help_after = n;
+        self
+    }
+    /// Remind the user of their previous input before every new attempt,
+    /// writing `(you entered: <input>)` before the message.
+    ///
+    /// This helps when the input was long or scrolled out of view.
+    /// The input is shown after applying the [`preparser`].
+    ///
+    /// [`preparser`]: #method.preparser
+    pub fn show_previous_input(mut self) -> Self {
+        self.config.show_previous_input = true;
         self
     }
     /// Feedback message to be displayed after the input has been succesfully processed.
+    ///
+    /// # Remarks
+    ///
+    /// Feedback is not displayed when the [`default_value`] is returned.
+    ///
+    /// [`default_value`]: #method.default_value
     pub fn feedback<F>(mut self, feedback: F) -> Self
     where
         F: Fn(&T) -> String + Send + Sync + 'static,
     {
         self.feedback = Arc::new(feedback);
+        self.timed_feedback = None;
+        self
+    }
+    /// Feedback message to be displayed after the input has been succesfully processed,
+    /// given the value and the time the user took to answer.
+    ///
+    /// The time is measured as in [`on_timing`], from the first message until the value is accepted.
+    ///
+    /// # Remarks
+    ///
+    /// This replaces the [`feedback`], and [`feedback`] replaces this.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// let _value = asking::question::<u32>()
+    ///     .feedback_with_timing(|value, elapsed| {
+    ///         format!("Answered in {:.1}s: {}\n", elapsed.as_secs_f64(), value)
+    ///     })
+    ///     .ask_and_wait();
+    /// ```
+    ///
+    /// [`on_timing`]: #method.on_timing
+    /// [`feedback`]: #method.feedback
+    pub fn feedback_with_timing<F>(mut self, feedback: F) -> Self
+    where
+        F: Fn(&T, Duration) -> String + Send + Sync + 'static,
+    {
+        self.timed_feedback = Some(Arc::new(feedback));
+        self
+    }
+    /// Feedback message to be displayed after the input has been succesfully processed,
+    /// chosen as the message of the first predicate the value satisfies.
+    ///
+    /// If no predicate is satisfied, no feedback is displayed.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # async_std::task::block_on(async {
+    /// let is_two: fn(&u32) -> bool = |value| *value == 2;
+    /// let is_four: fn(&u32) -> bool = |value| *value == 4;
+    /// let _value = asking::question()
+    ///     .feedback_matching([(is_two, "You chose 2!"), (is_four, "You chose 4!")])
+    ///     .ask()
+    ///     .await?;
+    /// # Ok::<(), asking::error::ProcessingError>(())
+    /// # });
+    /// ```
+    pub fn feedback_matching<I, P, M>(self, cases: I) -> Self
+    where
+        I: IntoIterator<Item = (P, M)>,
+        P: Fn(&T) -> bool + Send + Sync + 'static,
+        M: ToString,
+    {
+        let cases: Vec<(P, String)> = cases
+            .into_iter()
+            .map(|(predicate, message)| (predicate, message.to_string()))
+            .collect();
+        self.feedback(move |value| {
+            cases
+                .iter()
+                .find(|(predicate, _)| predicate(value))
+                .map(|(_, message)| message.clone())
+                .unwrap_or_default()
+        })
+    }
+    /// Prefix every line of the message, help and feedback with `prefix`.
+    pub fn indent(mut self, prefix: impl ToString) -> Self {
+        self.config.indent = prefix.to_string();
+        self
+    }
+    /// Number of bytes of feedback written before each flush.
+    ///
+    /// Big feedback is then displayed progressively.
+    /// The default value is `4096`.
+    pub fn feedback_chunk_size(mut self, chunk_size: usize) -> Self {
+        self.config.feedback_chunk_size = chunk_size.max(1);
+        self
+    }
+    /// Append a newline to the feedback, as the default [`error_formatter`] does with errors.
+    ///
+    /// The default is to display the feedback as it is. Empty feedback stays empty.
+    ///
+    /// [`error_formatter`]: #method.error_formatter
+    pub fn feedback_newline(mut self) -> Self {
+        self.config.feedback_newline = true;
+        self
+    }
+    /// Ignore errors while writing the message, help and feedback, and continue reading input.
+    ///
+    /// Useful when the output is best-effort, like a log pipe that might be closed,
+    /// but the input still matters.
+    ///
+    /// # Remarks
+    ///
+    /// Errors while echoing input typed in a terminal, for example with [`live_preview`],
+    /// are not ignored. Neither are those of the writer given to [`log_outcome`].
+    ///
+    /// [`live_preview`]: #method.live_preview
+    /// [`log_outcome`]: #method.log_outcome
+    pub fn ignore_write_errors(mut self) -> Self {
+        self.config.ignore_write_errors = true;
+        self
+    }
+    /// Flush the output only before reading input and at the end,
+    /// instead of after every message.
+    ///
+    /// This gives a deterministic sequence of writes, which is useful for snapshot tests.
+    /// The [`feedback_chunk_size`] has no effect, since the feedback is flushed at the end.
+    ///
+    /// [`feedback_chunk_size`]: #method.feedback_chunk_size
+    pub fn buffered(mut self) -> Self {
+        self.config.buffered = true;
+        self
+    }
+    /// Message with placeholders, like `{min}`, replaced by the [`template_vars`].
+    ///
+    /// This keeps the text data-driven, for example, to localize it.
+    ///
+    /// # Remarks
+    ///
+    /// Placeholders without a variable are left as they are, braces included.
+    /// The variables apply to any message, including those set by [`repeat_message`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    ///
+    /// let vars = HashMap::from([("min", "1"), ("max", "10")]);
+    /// let question = asking::question::<u8>()
+    ///     .message_template("Enter a value between {min} and {max}: ")
+    ///     .template_vars(vars);
+    /// assert_eq!(question.render_prompt(), "Enter a value between 1 and 10: ");
+    /// ```
+    ///
+    /// [`template_vars`]: #method.template_vars
+    /// [`repeat_message`]: #method.repeat_message
+    pub fn message_template(self, template: impl ToString) -> Self {
+        self.message(template)
+    }
+    /// Variables that replace the placeholders of a [`message_template`].
+    ///
+    /// Calling it again adds to the previous variables, replacing those with the same name.
+    ///
+    /// [`message_template`]: #method.message_template
+    pub fn template_vars<I, K, V>(mut self, vars: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: ToString,
+        V: ToString,
+    {
+        let vars = vars
+            .into_iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()));
+        self.config.template_vars.extend(vars);
+        self
+    }
+
+    /// Returns what is written before the first input is read, without reading input.
+    ///
+    /// This is the [`sticky_header`] and the message, followed by the [`feedback from attempts`].
+    ///
+    /// [`sticky_header`]: #method.sticky_header
+    /// [`feedback from attempts`]: #method.attempts_with_feedback
+    pub fn render_prompt(&self) -> String {
+        let mut prompt =
+            self.header(self.attempt + 1) + &self.indented(&self.affixed_message(self.attempt + 1));
+        if let Some((left_attempts, feedback)) = &self.config.attempts {
+            prompt += &feedback(*left_attempts);
+        }
+        prompt
+    }
+
+    /// Set a prefix and a suffix that surround the message, if it is not empty.
+    ///
+    /// For example, `prompt_affixes("> ", ": ")` displays the message `Name` as `> Name: `.
+    pub fn prompt_affixes(self, prefix: impl ToString, suffix: impl ToString) -> Self {
+        self.prompt_prefix(prefix).prompt_suffix(suffix)
+    }
+
+    /// Set a prefix that goes before the message, if it is not empty.
+    pub fn prompt_prefix(mut self, prefix: impl ToString) -> Self {
+        self.config.prompt_affixes.0 = prefix.to_string();
+        self
+    }
+
+    /// Set a suffix that goes after the message, if it is not empty.
+    pub fn prompt_suffix(mut self, suffix: impl ToString) -> Self {
+        self.config.prompt_affixes.1 = suffix.to_string();
         self
     }
+
+    fn affixed_message(&self, attempt: usize) -> String {
+        let message = render_template(&(self.config.message)(attempt), &self.config.template_vars);
+        match message.is_empty() {
+            true => message,
+            false => format!(
+                "{}{}{}",
+                self.config.prompt_affixes.0, message, self.config.prompt_affixes.1
+            ),
+        }
+    }
+
+    fn indented(&self, text: &str) -> String {
+        text.split_inclusive('\n')
+            .map(|line| self.config.indent.clone() + line)
+            .collect()
+    }
 }
 
 /// # Testing value
@@ -189,6 +574,137 @@ impl<T, R, W> QuestionBuilder<T, R, W> {
         };
         self.test_with_feedback(test)
     }
+    /// Test if the value is equivalent, according to `eq`, to an element of an iterator,
+    /// displaying a message upon failure.
+    ///
+    /// This generalizes [`inside`] to custom equality, like a tolerance for floats.
+    ///
+    /// # Remarks
+    ///
+    /// To prevent infinite loops, make sure `iterator` is finite.
+    ///
+    /// [`inside`]: #method.inside
+    pub fn inside_by<I, F, M>(self, iterator: I, eq: F, message: M) -> Self
+    where
+        T: Send + Sync + 'static,
+        I: IntoIterator<Item = T>,
+        F: Fn(&T, &T) -> bool + Send + Sync + 'static,
+        M: ToString + Send + Sync + 'static,
+    {
+        let options: Vec<T> = iterator.into_iter().collect();
+        self.test_with_msg(
+            move |value: &T| options.iter().any(|option| eq(value, option)),
+            message,
+        )
+    }
+    /// Add a new asynchronous test for the value, displaying its error upon failure.
+    ///
+    /// Useful for slow checks, like asking a server if a username is available.
+    /// The test can keep the user informed through the [`Progress`] handle,
+    /// whose reports are passed to the callback set by [`progress`].
+    ///
+    /// # Remarks
+    ///
+    /// Asynchronous tests run after all other tests, in the order they were added.
+    /// They are not run by [`validate`], which is synchronous.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// let name = asking::text()
+    ///     .reader("ferris\n".as_bytes())
+    ///     .progress(|message| eprintln!("{}", message))
+    ///     .async_test(|name: String, progress| async move {
+    ///         progress.report("checking availability...");
+    ///         match name.as_str() {
+    ///             "admin" => Err(eyre::eyre!("The name is taken.")),
+    ///             _ => Ok(()),
+    ///         }
+    ///     })
+    ///     .ask()
+    ///     .await?;
+    /// assert_eq!(name, "ferris");
+    /// # Ok::<(), asking::error::ProcessingError>(())
+    /// # });
+    /// ```
+    ///
+    /// [`Progress`]: struct.Progress.html
+    /// [`progress`]: #method.progress
+    /// [`validate`]: #method.validate
+    pub fn async_test<F, Fut>(mut self, test: F) -> Self
+    where
+        T: Clone + Send + 'static,
+        F: Fn(T, Progress) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = eyre::Result<()>> + Send + 'static,
+    {
+        self.async_tests.push(Arc::new(move |value: &T, progress| {
+            Box::pin(test(value.clone(), progress))
+        }));
+        self
+    }
+    /// Add a new test for the value that runs on a thread where blocking is acceptable.
+    ///
+    /// Useful for slow computations, like checking if a big number is prime,
+    /// without starving the executor. It is an [`async_test`] that offloads the predicate.
+    ///
+    /// # Remarks
+    ///
+    /// There is a default message that you might want to change.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let is_prime = |n: &u64| *n > 1 && (2..*n).take_while(|d| d * d <= *n).all(|d| n % d != 0);
+    /// let prime = asking::question::<u64>()
+    ///     .reader("1000000\n1000003\n".as_bytes())
+    ///     .test_blocking_with_msg(is_prime, "The number is not prime.")
+    ///     .ask_and_wait();
+    /// assert_eq!(prime.unwrap(), 1000003);
+    /// ```
+    ///
+    /// [`async_test`]: #method.async_test
+    pub fn test_blocking<F>(self, test: F) -> Self
+    where
+        T: Clone + Send + 'static,
+        F: Fn(&T) -> bool + Send + Sync + 'static,
+    {
+        self.test_blocking_with_msg(test, "The value failed a test.")
+    }
+    /// Add a new test for the value that runs on a thread where blocking is acceptable,
+    /// displaying a message upon failure.
+    pub fn test_blocking_with_msg<F, M>(self, test: F, message: M) -> Self
+    where
+        T: Clone + Send + 'static,
+        F: Fn(&T) -> bool + Send + Sync + 'static,
+        M: ToString + Send + Sync + 'static,
+    {
+        let test = Arc::new(test);
+        let message = Arc::new(message);
+        self.async_test(move |value: T, _| {
+            let (test, message) = (test.clone(), message.clone());
+            async move {
+                match async_std::task::spawn_blocking(move || test(&value)).await {
+                    true => Ok(()),
+                    false => Err(Report::msg(message.to_string())),
+                }
+            }
+        })
+    }
+    /// Accept the value right away if it passes `predicate`, skipping all tests.
+    ///
+    /// This is useful for special cases, like an override code that is otherwise out of range.
+    ///
+    /// # Remarks
+    ///
+    /// The predicate is checked before any test. Calling it again replaces the predicate.
+    pub fn accept_if<F>(mut self, predicate: F) -> Self
+    where
+        F: Fn(&T) -> bool + Send + Sync + 'static,
+    {
+        self.accept_if = Some(Arc::new(predicate));
+        self
+    }
 }
 
 /// # Testing value extended
@@ -230,6 +746,40 @@ where
         )
     }
 
+    /// Test if the value is inside a range, like `5..100` or `5..=100`.
+    ///
+    /// Unlike [`inside`], the range is not collected, so it works for large ranges
+    /// and for types that can not be iterated, like floating-point numbers.
+    ///
+    /// # Remarks
+    ///
+    /// There is a default message you might want to change.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let question = asking::question::<f64>().inside_range(0.0..1.0);
+    /// assert!(question.validate("0.5").is_ok());
+    /// assert!(question.validate("1").is_err());
+    /// ```
+    ///
+    /// [`inside`]: #method.inside
+    pub fn inside_range<B>(self, range: B) -> Self
+    where
+        B: RangeBounds<T> + Send + Sync + 'static,
+    {
+        self.inside_range_with_msg(range, "Value is not inside the range.")
+    }
+
+    /// Test if the value is inside a range, displaying a message upon failure.
+    pub fn inside_range_with_msg<B, M>(self, range: B, message: M) -> Self
+    where
+        B: RangeBounds<T> + Send + Sync + 'static,
+        M: ToString + Send + Sync + 'static,
+    {
+        self.test_with_msg(move |value: &T| range.contains(value), message)
+    }
+
     /// Test if the value is at most `upper_bound`.
     ///
     /// # Remarks
@@ -304,36 +854,320 @@ where
     }
 }
 
-/// # Useful settings
-impl<T, R, W> QuestionBuilder<T, R, W> {
-    /// Bound the number of possible attempts.
+/// # Numeric value
+impl<T, R, W> QuestionBuilder<T, R, W>
+where
+    T: Integer + ToString + Send + Sync + 'static,
+{
+    /// Test if the value is a multiple of `step`.
     ///
-    /// The default value is `None`, which gives infinite attempts to the user.
-    pub fn attempts<O>(mut self, attempts: O) -> Self
+    /// # Remarks
+    ///
+    /// A `step` equal to zero adds no test.
+    /// Also, there is a default message you might want to change.
+    pub fn step(self, step: T) -> Self {
+        let message = format!("The value must be a multiple of {}.", step.to_string());
+        self.step_with_msg(step, message)
+    }
+
+    /// Test if the value is a multiple of `step`, displaying a message upon failure.
+    ///
+    /// # Remarks
+    ///
+    /// A `step` equal to zero adds no test.
+    pub fn step_with_msg<M>(self, step: T, message: M) -> Self
     where
-        O: Into<Option<usize>>,
+        M: ToString + Send + Sync + 'static,
     {
-        match attempts.into() {
-            Some(attempts) => self.attempts_with_feedback(attempts, |_| "".to_string()),
-            None => {
-                self.attempts = None;
+        if step.is_zero() {
+            return self;
+        }
+        self.test_with_msg(move |value: &T| value.is_multiple(step), message)
+    }
+}
+
+macro_rules! impl_float {
+    ($($t:ident),*) => {$(
+        /// # Floating-point value
+        impl<R, W> QuestionBuilder<$t, R, W> {
+            /// Round the accepted value to `decimals` decimal places.
+            ///
+            /// This is useful for currency or measurements, where more precision is not wanted.
+            ///
+            /// # Remarks
+            ///
+            /// Rounding happens after the tests, so they see the value as parsed,
+            /// and before the confirmation and [`feedback`].
+            /// Values are left unchanged if `decimals` is at least the number of significant
+            /// digits of the type (`DIGITS`), or if they are too big to be rounded.
+            ///
+            /// [`feedback`]: #method.feedback
+            pub fn round_to(mut self, decimals: u32) -> Self {
+                if decimals >= $t::DIGITS {
+                    self.round = None;
+                    return self;
+                }
+                let factor = (10 as $t).powi(decimals as i32);
+                self.round = Some(Arc::new(move |value| {
+                    let scaled = value * factor;
+                    match scaled.is_finite() {
+                        true => scaled.round() / factor,
+                        false => value,
+                    }
+                }));
+                self
+            }
+        }
+    )*};
+}
+
+impl_float!(f64, f32);
+
+macro_rules! impl_unsigned {
+    ($($t:ty),*) => {$(
+        /// # Unsigned integer value
+        impl<R, W> QuestionBuilder<$t, R, W> {
+            /// Replace the errors of the parser by clearer messages,
+            /// and reject input with a sign, like `-1` or `+5`.
+            ///
+            /// For example, `-1` is rejected with
+            /// "This must be a non-negative whole number." instead of "invalid digit found in string".
+            ///
+            /// # Remarks
+            ///
+            /// This also activates the feedback from the parser,
+            /// so that the messages are displayed.
+            pub fn friendly_numeric_errors(mut self) -> Self {
+                let parser = self.parser.0.clone();
+                self.parser = (
+                    Arc::new(move |s| match unsigned_sign_error(s) {
+                        Some(message) => Err(Report::msg(message)),
+                        None => parser(s)
+                            .map_err(|_| Report::msg(unsigned_parse_error(s, <$t>::MAX))),
+                    }),
+                    true,
+                );
                 self
             }
         }
+    )*};
+}
+
+impl_unsigned!(u8, u16, u32, u64, u128, usize);
+
+/// Explains why `input` with a sign is not an unsigned integer.
+fn unsigned_sign_error(input: &str) -> Option<String> {
+    match input.chars().next() {
+        Some('-') => Some("This must be a non-negative whole number.".to_string()),
+        Some('+') => Some(format!(
+            "This must be a whole number without a sign, like {}.",
+            input.trim_start_matches('+')
+        )),
+        _ => None,
     }
+}
 
-    /// Bound the number of possible attempts, displaying a message before any input is read.
+/// Explains why `input` could not be parsed as an unsigned integer up to `max`.
+fn unsigned_parse_error(input: &str, max: impl ToString) -> String {
+    match !input.is_empty() && input.bytes().all(|b| b.is_ascii_digit()) {
+        true => format!("This must be at most {}.", max.to_string()),
+        false => "This must be a non-negative whole number.".to_string(),
+    }
+}
+
+/// # Useful settings
+impl<T, R, W> QuestionBuilder<T, R, W> {
+    /// Bound the number of possible attempts.
     ///
     /// The default value is `None`, which gives infinite attempts to the user.
     ///
     /// # Remarks
     ///
-    /// Before the first attempt, a message will be displayed, so make sure to handle that case.
-    pub fn attempts_with_feedback<F>(mut self, attempts: usize, feedback: F) -> Self
+    /// Convenience method for [`retry_policy`] with [`RetryPolicy::Fixed`] or [`RetryPolicy::Unlimited`].
+    ///
+    /// [`retry_policy`]: #method.retry_policy
+    /// [`RetryPolicy::Fixed`]: enum.RetryPolicy.html#variant.Fixed
+    /// [`RetryPolicy::Unlimited`]: enum.RetryPolicy.html#variant.Unlimited
+    pub fn attempts<O>(self, attempts: O) -> Self
+    where
+        O: Into<Option<usize>>,
+    {
+        match attempts.into() {
+            Some(attempts) => self.retry_policy(RetryPolicy::Fixed(attempts)),
+            None => self.retry_policy(RetryPolicy::Unlimited),
+        }
+    }
+
+    /// Set the number of possible attempts and the delay between them.
+    ///
+    /// The default value is [`RetryPolicy::Unlimited`].
+    ///
+    /// # Remarks
+    ///
+    /// This forgets any feedback set by [`attempts_with_feedback`].
+    ///
+    /// [`RetryPolicy::Unlimited`]: enum.RetryPolicy.html#variant.Unlimited
+    /// [`attempts_with_feedback`]: #method.attempts_with_feedback
+    pub fn retry_policy(mut self, policy: RetryPolicy) -> Self {
+        let (attempts, delay) = match policy {
+            RetryPolicy::Fixed(attempts) => (Some(attempts), Duration::default()),
+            RetryPolicy::WithDelay { attempts, delay } => (Some(attempts), delay),
+            RetryPolicy::Unlimited => (None, Duration::default()),
+        };
+        self.config.retry_delay = delay;
+        match attempts {
+            Some(attempts) => self.attempts_with_feedback(attempts, |_| "".to_string()),
+            None => {
+                self.config.attempts = None;
+                self
+            }
+        }
+    }
+
+    /// Bound the number of possible attempts, displaying a message before any input is read.
+    ///
+    /// The default value is `None`, which gives infinite attempts to the user.
+    ///
+    /// # Remarks
+    ///
+    /// Before the first attempt, a message will be displayed, so make sure to handle that case.
+    pub fn attempts_with_feedback<F>(mut self, attempts: usize, feedback: F) -> Self
     where
         F: Fn(usize) -> String + Send + Sync + 'static,
     {
-        self.attempts = Some((attempts, Arc::new(feedback)));
+        self.config.attempts = Some((attempts, Arc::new(feedback)));
+        self
+    }
+
+    /// Return the [`default_value`] when there are no more attempts,
+    /// instead of the error [`NoMoreAttempts`].
+    ///
+    /// # Remarks
+    ///
+    /// The default value is returned even if the input is [`required`].
+    /// If there is no default value, the error [`NoMoreAttempts`] is still returned.
+    ///
+    /// [`default_value`]: #method.default_value
+    /// [`NoMoreAttempts`]: error/enum.ProcessingError.html#variant.NoMoreAttempts
+    /// [`required`]: #method.required
+    pub fn attempts_then_default(mut self) -> Self {
+        self.config.attempts_then_default = true;
+        self
+    }
+
+    /// Take the answer from the content of the file at `path`, without prompting.
+    ///
+    /// This is useful for secrets given as files, like Docker or systemd credentials.
+    /// The content is trimmed and then validated as usual, but there is a single attempt.
+    ///
+    /// # Errors
+    ///
+    /// If the file can not be read, the error [`SecretUnreadable`] is returned.
+    /// If its content is not valid, the error [`InvalidSecret`] is returned.
+    ///
+    /// [`SecretUnreadable`]: error/enum.ProcessingError.html#variant.SecretUnreadable
+    /// [`InvalidSecret`]: error/enum.ProcessingError.html#variant.InvalidSecret
+    pub fn secret_from_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.config.secret_from_path = Some(path.into());
+        self
+    }
+
+    /// Skip reading input, returning the [`default_value`] right after displaying the message.
+    ///
+    /// This is useful to check how a program prompts, for example, in continuous integration,
+    /// without supplying any input. The [`feedback`] is still given on the default value.
+    ///
+    /// # Errors
+    ///
+    /// If there is no default value, the error [`NoDefault`] is returned.
+    ///
+    /// [`default_value`]: #method.default_value
+    /// [`feedback`]: #method.feedback
+    /// [`NoDefault`]: error/enum.ProcessingError.html#variant.NoDefault
+    pub fn dry_run(mut self) -> Self {
+        self.config.dry_run = true;
+        self
+    }
+
+    /// Restore the initial number of attempts when a value passes `predicate`,
+    /// but fails a later [`test`].
+    ///
+    /// This rewards partially correct answers, for example, in games.
+    ///
+    /// # Remarks
+    ///
+    /// Only has an effect if the number of [`attempts`] is bounded.
+    ///
+    /// [`test`]: #method.test
+    /// [`attempts`]: #method.attempts
+    pub fn reset_attempts_on<F>(mut self, predicate: F) -> Self
+    where
+        F: Fn(&T) -> bool + Send + Sync + 'static,
+    {
+        self.reset_attempts_on = Some(Arc::new(predicate));
+        self
+    }
+
+    /// Bound the total number of bytes read while asking, across all attempts.
+    ///
+    /// Once more than `max_bytes` are read, the error [`InputTooLong`] is returned.
+    /// This bounds the resources a peer can consume, for example, by sending many invalid lines.
+    ///
+    /// # Remarks
+    ///
+    /// Every line counts towards the budget, including confirmations.
+    /// To bound the length of each input, check out [`max_length`].
+    ///
+    /// [`InputTooLong`]: error/enum.ProcessingError.html#variant.InputTooLong
+    /// [`max_length`]: #method.max_length
+    pub fn session_budget(mut self, max_bytes: usize) -> Self {
+        self.config.session_budget = Some(max_bytes);
+        self
+    }
+
+    /// Call `on_timing` with the time the user took to answer,
+    /// from the first message until the value is accepted.
+    ///
+    /// # Remarks
+    ///
+    /// It is only called if a value is returned.
+    pub fn on_timing<F>(mut self, on_timing: F) -> Self
+    where
+        F: Fn(Duration) + Send + Sync + 'static,
+    {
+        self.config.on_timing = Some(Arc::new(on_timing));
+        self
+    }
+
+    /// Write a one-line summary of the outcome of asking to `writer`,
+    /// separate from the interactive writer.
+    ///
+    /// This is useful for audit trails. The line says whether a value was returned,
+    /// the value or the error, and how many inputs were read, for example,
+    /// `outcome=success attempts=2 value=7` or `outcome=failure attempts=3 error="..."`.
+    ///
+    /// # Remarks
+    ///
+    /// It is written for both values and errors. Errors while writing the summary are ignored,
+    /// so that they never hide the outcome of asking.
+    pub fn log_outcome<L>(mut self, writer: L) -> Self
+    where
+        L: Write + Unpin + Send + Sync + 'static,
+        T: Debug,
+    {
+        let format: Feedback<T> = Arc::new(|value: &T| format!("{:?}", value));
+        self.outcome_log = Some((Box::new(writer), format));
+        self
+    }
+
+    /// Call `callback` with the progress reported by [`async_test`]s while they run.
+    ///
+    /// [`async_test`]: #method.async_test
+    pub fn progress<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&str) + Send + Sync + 'static,
+    {
+        self.config.progress = Some(Arc::new(callback));
         self
     }
 
@@ -342,6 +1176,9 @@ impl<T, R, W> QuestionBuilder<T, R, W> {
     /// # Remarks
     ///
     /// Default values are NOT tested, so make sure that it is a value that passes your tests!
+    /// Also, no [`feedback`] is displayed for them.
+    ///
+    /// [`feedback`]: #method.feedback
     pub fn default_value<S>(mut self, value: S) -> Self
     where
         S: Into<Option<T>>,
@@ -350,27 +1187,194 @@ impl<T, R, W> QuestionBuilder<T, R, W> {
         self
     }
 
+    /// Give a default value from the first of the `sources` that yields a value,
+    /// parsed by the [`parser`].
+    ///
+    /// Sources are resolved every time the question is asked, in order,
+    /// skipping those that are missing or can not be parsed.
+    /// A file that can not be read is skipped as if it was missing, without any error.
+    /// If no source yields a value, the [`default_value`] is kept.
+    ///
+    /// # Remarks
+    ///
+    /// As with [`default_value`], the default value is NOT tested.
+    ///
+    /// # Examples
+    ///
+    /// Use a command-line argument, an environment variable, a configuration file,
+    /// and finally a fixed value, in that order.
+    /// ```no_run
+    /// use asking::DefaultSource;
+    ///
+    /// let port = asking::question::<u16>()
+    ///     .message("Port: ")
+    ///     .default_chain(vec![
+    ///         DefaultSource::Arg(std::env::args().nth(1)),
+    ///         DefaultSource::Env("APP_PORT".to_string()),
+    ///         DefaultSource::File("port.txt".into()),
+    ///         DefaultSource::Literal(8080),
+    ///     ])
+    ///     .ask_and_wait();
+    /// ```
+    ///
+    /// [`parser`]: #method.parser
+    /// [`default_value`]: #method.default_value
+    pub fn default_chain(mut self, sources: Vec<DefaultSource<T>>) -> Self
+    where
+        T: Clone + Send + Sync + 'static,
+    {
+        let sources = Arc::new(sources);
+        self.default_chain = Some(Arc::new(move |parser| {
+            let sources = sources.clone();
+            Box::pin(async move {
+                for source in sources.iter() {
+                    if let Some(value) = source.resolve(&parser).await {
+                        return Some(value);
+                    }
+                }
+                None
+            })
+        }));
+        self
+    }
+
+    /// Set what input counts as empty, for the [`default_value`] and [`required`] methods.
+    ///
+    /// The predicate is applied after the [`preparser`].
+    /// The default value is `str::is_empty`.
+    ///
+    /// [`default_value`]: #method.default_value
+    /// [`required`]: #method.required
+    /// [`preparser`]: #method.preparser
+    pub fn is_empty_when<F>(mut self, is_empty: F) -> Self
+    where
+        F: Fn(&str) -> bool + Send + Sync + 'static,
+    {
+        self.config.is_empty_when = Arc::new(is_empty);
+        self
+    }
+
+    /// Return the [`default_value`] when the input can not be parsed, instead of asking again.
+    ///
+    /// This is a lenient mode, where any unparseable input counts as choosing the default.
+    ///
+    /// # Remarks
+    ///
+    /// It only applies if there is a default value and the input is not [`required`].
+    /// The default value is returned on the first parsing error, even if there are
+    /// [`attempts`] left, but failing [`test`]s still consume attempts as usual.
+    /// The parsing error is displayed if the [`parser_feedback`] is on.
+    ///
+    /// [`default_value`]: #method.default_value
+    /// [`required`]: #method.required
+    /// [`attempts`]: #method.attempts
+    /// [`test`]: #method.test
+    /// [`parser_feedback`]: #method.parser_feedback_toggle
+    pub fn parse_error_uses_default(mut self) -> Self {
+        self.config.parse_error_uses_default = true;
+        self
+    }
+
     /// Requires that the input is not empty to continue.
     ///
     /// # Remarks
     ///
     /// There is a default message you might want to change.
     pub fn required(mut self) -> Self {
-        self.required.1 = true;
+        self.config.required = true;
         self
     }
 
     /// Requires that the input is not empty to continue, displaying a message upon failure.
     pub fn required_with_msg(mut self, message: impl ToString) -> Self {
-        self.required = (message.to_string(), true);
+        self.config.required_message = message.to_string();
+        self.config.required = true;
+        self
+    }
+
+    /// Set whether the help is displayed before (`true`) or after (`false`)
+    /// the message of [`required`] input, when the input is empty.
+    ///
+    /// The default value is `true`.
+    ///
+    /// [`required`]: #method.required
+    pub fn required_help_first(mut self, first: bool) -> Self {
+        self.config.required_help = Some(first);
+        self
+    }
+
+    /// Do not display the help when the input is empty but [`required`],
+    /// only the required message.
+    ///
+    /// [`required`]: #method.required
+    pub fn required_without_help(mut self) -> Self {
+        self.config.required_help = None;
+        self
+    }
+
+    /// Ask to type the input again, and only accept it if both entries match.
+    ///
+    /// The `message` is displayed before reading the confirmation.
+    /// Upon a mismatch, only the confirmation is asked again (check out [`confirm_retries`]).
+    ///
+    /// # Remarks
+    ///
+    /// Entries are compared after applying the [`preparser`].
+    /// There is a default message upon a mismatch you might want to change.
+    ///
+    /// [`confirm_retries`]: #method.confirm_retries
+    /// [`preparser`]: #method.preparser
+    pub fn confirm(self, message: impl ToString) -> Self {
+        self.confirm_with_msg(message, "The entries do not match.")
+    }
+
+    /// Ask to type the input again, displaying `mismatch` if both entries do not match.
+    pub fn confirm_with_msg(mut self, message: impl ToString, mismatch: impl ToString) -> Self {
+        self.config.confirmation = Some((message.to_string(), mismatch.to_string()));
+        self
+    }
+
+    /// Number of times the confirmation is asked again after a mismatch,
+    /// before returning the error [`ConfirmationMismatch`].
+    ///
+    /// The default value is `2`.
+    ///
+    /// [`ConfirmationMismatch`]: error/enum.ProcessingError.html#variant.ConfirmationMismatch
+    pub fn confirm_retries(mut self, retries: usize) -> Self {
+        self.config.confirm_retries = retries;
+        self
+    }
+
+    /// Display the value with `template` and ask whether it is correct, as a yes/no question.
+    ///
+    /// The value is only returned upon a yes, while a no starts a new attempt.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// let _age = asking::question::<u8>()
+    ///     .confirm_value(|age| format!("You entered {}, is this correct? (y/n) ", age))
+    ///     .ask_and_wait();
+    /// ```
+    pub fn confirm_value<F>(mut self, template: F) -> Self
+    where
+        F: Fn(&T) -> String + Send + Sync + 'static,
+    {
+        self.confirm_value = Some(Arc::new(move |value| Some(template(value))));
         self
     }
 
     /// Toggles between requiring and not requiring input.
-    pub fn required_toogle(mut self) -> Self {
-        self.required.1 = !self.required.1;
+    pub fn required_toggle(mut self) -> Self {
+        self.config.required = !self.config.required;
         self
     }
+
+    /// Toggles between requiring and not requiring input.
+    #[deprecated(since = "0.0.3", note = "use `required_toggle` instead")]
+    pub fn required_toogle(self) -> Self {
+        self.required_toggle()
+    }
 }
 
 /// # Executors
@@ -384,488 +1388,4564 @@ impl<T, R, W> QuestionBuilder<T, R, W> {
     /// This time corresponds to the whole execution, including displaying feedback.
     /// So the user might enter valid input before the time runs out,
     /// but the whole process might still timeout.
+    /// Check out [`timeout_input_only`] to only account for the time spent reading input.
+    ///
+    /// [`timeout_input_only`]: #method.timeout_input_only
     pub fn timeout(mut self, duration: Duration) -> Self {
-        self.executor = Executor::Timeout(duration);
+        self.config.executor = Executor::Timeout(duration);
         self
     }
-}
 
-/// # Prompt functionalities
-impl<T, R, W> QuestionBuilder<T, R, W>
-where
-    W: Write + Unpin,
-    R: Read + Unpin,
-{
-    /// Asynchronously gets input from the user.
+    /// Set a maximum time for the user to input an answer.
     ///
-    /// The detailed process is as follows.
-    ///
-    /// 0. Check there are [`attempts`] left
-    /// 1. Write [`message`]
-    /// 2. Write [`feedback from attempts`]
-    /// 3. Read input
-    /// 4. Apply the [`preparser`] to the input
-    /// 5. Return [`default_value`] if it corresponds
-    /// 6. Apply all [`str_test`]s
-    /// 4. Convert the input with [`parser`]
-    /// 5. Apply all [`test`]s
-    /// 6. Write [`feedback`]
-    /// 7. Return the value
+    /// # Remarks
     ///
-    /// [`attempts`]: #method.attempts
-    /// [`message`]: #method.message
-    /// [`feedback from attempts`]: #method.attempts_with_feedback
-    /// [`preparser`]: #method.preparser
-    /// [`default_value`]: #method.default_value
-    /// [`str_test`]: #method.str_test
-    /// [`parser`]: #method.parser
-    /// [`test`]: #method.test
-    /// [`feedback`]: #method.feedback
+    /// Only the time spent reading input counts, adding up over all attempts.
+    /// So, writing messages, help and feedback does not consume this time.
+    pub fn timeout_input_only(mut self, duration: Duration) -> Self {
+        self.config.executor = Executor::TimeoutInputOnly(duration);
+        self
+    }
+
+    /// Set a maximum time for the user to be idle while answering the question.
     ///
     /// # Remarks
     ///
-    /// There are two types of messages that can be displayed during the process:
-    /// feedback and error messages.
-    /// Feedback is displayed as given by the corresponding method.
-    /// Error messages are displayed after applying [`error_formatter`].
+    /// The time is reset every time some input is read, so only a pause longer than
+    /// `duration` times out.
+    pub fn idle_timeout(mut self, duration: Duration) -> Self {
+        self.config.executor = Executor::IdleTimeout(duration);
+        self
+    }
+
+    /// Stop asking as soon as `future` completes, returning the error [`Cancelled`].
     ///
-    /// More over, error messages are displayed through the [`eyre`] crate.
-    /// Therefore, you can further configure their display
-    /// (check out [`EyreHandler`]). [`error_formatter`]
-    /// is applied on top of displaying the error.
+    /// This integrates the question with your own shutdown signals.
+    /// It works together with any of the executors above.
     ///
-    /// [`error_formatter`]: #method.error_formatter
-    /// [`eyre`]: https://crates.io/crates/eyre
-    /// [`EyreHandler`]: https://docs.rs/eyre/0.6.5/eyre/trait.EyreHandler.html
-    pub async fn ask(self) -> Result<T, ProcessingError> {
-        match self.executor {
-            Executor::None => self.ask_loop().await,
-            Executor::Timeout(duration) => {
-                async_std::future::timeout(duration, self.ask_loop()).await?
-            }
-        }
+    /// # Examples
+    ///
+    /// ```
+    /// use asking::error::ProcessingError;
+    ///
+    /// let (shutdown, signal) = async_std::channel::bounded::<()>(1);
+    /// drop(shutdown);
+    /// let result = asking::text()
+    ///     .reader(async_std::io::stdin())
+    ///     .cancel_on(async move {
+    ///         signal.recv().await.ok();
+    ///     })
+    ///     .ask_and_wait();
+    /// assert!(matches!(result, Err(ProcessingError::Cancelled)));
+    /// ```
+    ///
+    /// [`Cancelled`]: error/enum.ProcessingError.html#variant.Cancelled
+    pub fn cancel_on<F>(mut self, future: F) -> Self
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        self.config.cancel_on = Some(Cancellation::new(future));
+        self
     }
+}
 
-    /// Synchronously gets input from the user.
-    ///
-    /// Convenience method for `async_std::task::block_on(self.ask())`.
+/// # Terminal
+///
+/// Some functionalities are only available if reader and writer are an interactive terminal.
+impl<T, R, W> QuestionBuilder<T, R, W> {
+    /// Declare whether the reader and writer are an interactive terminal.
     ///
     /// # Remarks
     ///
-    /// Although this method goes against the goal of this crate,
-    /// it is useful for quick implementations.
-    pub fn ask_and_wait(self) -> Result<T, ProcessingError> {
-        async_std::task::block_on(self.ask())
+    /// For the standard input and output, this is detected automatically.
+    /// Changing the [`reader`] or [`writer`] resets this to `false`.
+    ///
+    /// [`reader`]: #method.reader
+    /// [`writer`]: #method.writer
+    pub fn terminal(mut self, terminal: bool) -> Self {
+        self.config.terminal = terminal;
+        self
     }
 
-    async fn ask_loop(mut self) -> Result<T, ProcessingError> {
-        loop {
-            self.check_attempts()?;
-            self.write_message().await?;
-            self.write_attempts_feedback().await?;
-            let preinput = self.take_input().await?;
-            self.decrease_attempts()?;
-            let input = (self.preparser)(preinput);
-            if input.is_empty() && !self.required.1 && self.default.is_some() {
-                return Ok(self.default.unwrap());
-            }
-            if self.test_string(&input).await.is_err() {
-                continue;
-            };
-            let proposal = match self.parse_input(&input).await {
-                Ok(value) => value,
-                Err(_) => continue,
-            };
-            if self.test_proposal(&proposal).await.is_err() {
-                continue;
-            }
-            self.give_feedback(&proposal).await?;
+    /// Do not echo the input while the user types it.
+    ///
+    /// # Remarks
+    ///
+    /// Echo is disabled in the terminal of the standard input, and only on unix platforms.
+    /// Outside a terminal, input is read as usual, unless [`require_tty_for_mask`] is set.
+    /// For other readers declared as a terminal with [`terminal`], disabling their echo
+    /// is left to you.
+    ///
+    /// [`require_tty_for_mask`]: #method.require_tty_for_mask
+    /// [`terminal`]: #method.terminal
+    pub fn mask(mut self) -> Self {
+        self.config.mask = true;
+        self
+    }
 
-            return Ok(proposal);
-        }
+    /// Display how many characters of the masked input have been typed, like `(12 chars)`,
+    /// without revealing its content.
+    ///
+    /// This helps users confirm, for example, that a pasted password landed.
+    ///
+    /// # Remarks
+    ///
+    /// The terminal of the standard input is put in raw mode to count the characters as they
+    /// are typed, and only on unix platforms. Outside a terminal, nothing is displayed.
+    pub fn mask_length_indicator(mut self) -> Self {
+        self.config.mask_length_indicator = true;
+        self
     }
 
-    fn check_attempts(&mut self) -> Result<(), ProcessingError> {
-        match self.attempts {
-            Some((0, _)) => Err(ProcessingError::NoMoreAttempts),
-            _ => Ok(()),
-        }
+    /// Refuse to read masked input when it can not be masked,
+    /// returning the error [`MaskUnavailable`] instead.
+    ///
+    /// This prevents secrets from being echoed, for example, into logs.
+    ///
+    /// [`MaskUnavailable`]: error/enum.ProcessingError.html#variant.MaskUnavailable
+    pub fn require_tty_for_mask(mut self) -> Self {
+        self.config.require_tty_for_mask = true;
+        self
     }
 
-    async fn write_message(&mut self) -> Result<(), std::io::Error> {
-        self.writer.write(self.message.0.as_bytes()).await?;
-        self.writer.flush().await?;
-        if !self.message.1 {
-            self.message = ("".to_string(), false);
-        }
-        Ok(())
+    /// Toggles between requiring and not requiring a terminal for masked input.
+    pub fn require_tty_for_mask_toggle(mut self) -> Self {
+        self.config.require_tty_for_mask = !self.config.require_tty_for_mask;
+        self
     }
 
-    async fn write_attempts_feedback(&mut self) -> Result<(), std::io::Error> {
-        if let Some((left_attempts, feedback)) = &self.attempts {
-            self.writer
-                .write((feedback)(*left_attempts).as_bytes())
-                .await?;
-            self.writer.flush().await?;
-        };
-        Ok(())
+    /// Display, below the input line, a preview of the input while the user types it.
+    ///
+    /// For example, the preview can show how the input is going to be interpreted.
+    ///
+    /// # Remarks
+    ///
+    /// The terminal of the standard input is put in raw mode to read the input as it is typed,
+    /// and only on unix platforms. Outside a terminal, or for [`mask`]ed input,
+    /// input is read as usual and nothing is displayed.
+    /// Other readers declared as a terminal with [`terminal`] are expected to be in raw mode.
+    ///
+    /// [`mask`]: #method.mask
+    /// [`terminal`]: #method.terminal
+    pub fn live_preview<F>(mut self, formatter: F) -> Self
+    where
+        F: Fn(&str) -> String + Send + Sync + 'static,
+    {
+        self.config.live_preview = Some(Arc::new(formatter));
+        self
     }
 
-    async fn take_input(&mut self) -> Result<String, ProcessingError> {
-        let mut input = String::new();
-        if self.reader.read_line(&mut input).await? == 0 {
-            return Err(ProcessingError::Eof);
-        }
-        Ok(input)
+    /// Start the input line with `text`, which the user can edit or accept by pressing Enter.
+    ///
+    /// This is more natural than displaying a default value, like shells do with history.
+    ///
+    /// # Remarks
+    ///
+    /// The terminal of the standard input is put in raw mode to read the input as it is typed,
+    /// and only on unix platforms. Outside a terminal, or for [`mask`]ed input,
+    /// it is ignored and input is read as usual.
+    ///
+    /// [`mask`]: #method.mask
+    pub fn prefill(mut self, text: impl ToString) -> Self {
+        self.config.prefill = Some(text.to_string());
+        self
     }
 
-    fn decrease_attempts(&mut self) -> Result<(), ProcessingError> {
-        if let Some((left_attempts, _)) = &mut self.attempts {
-            *left_attempts -= 1;
-        }
-        Ok(())
+    /// Display a link to `url`, with `text`, after the [`feedback`].
+    ///
+    /// In a terminal, the link is a clickable hyperlink (OSC 8 escape sequence)
+    /// showing only `text`. Otherwise, it is displayed as `text (url)`.
+    ///
+    /// [`feedback`]: #method.feedback
+    pub fn feedback_link(mut self, text: impl ToString, url: impl ToString) -> Self {
+        self.config.feedback_link = Some((text.to_string(), url.to_string()));
+        self
     }
 
-    async fn test_string(&mut self, str_proposal: &str) -> eyre::Result<()> {
-        for str_test in &self.str_tests {
-            let result = (str_test.0)(str_proposal);
-            if let Err(ref e) = result {
-                if str_test.1 {
-                    self.writer
-                        .write((self.error_formatter)(e.to_string()).as_bytes())
-                        .await?;
-                    self.writer.flush().await?;
-                }
-                self.display_help().await?;
-                return result;
-            }
+    fn hyperlink(&self, text: &str, url: &str) -> String {
+        match self.config.terminal {
+            true => format!("\x1b]8;;{}\x1b\\{}\x1b]8;;\x1b\\", url, text),
+            false => format!("{} ({})", text, url),
         }
-        Ok(())
     }
 
-    async fn parse_input(&mut self, input: &str) -> eyre::Result<T> {
-        if input == "" && self.required.1 {
-            self.display_help().await?;
-            self.writer
-                .write((self.error_formatter)(self.required.0.clone()).as_bytes())
-                .await?;
-            self.writer.flush().await?;
-        }
-        let result = (self.parser.0)(input);
-        if let Err(ref e) = result {
-            self.display_help().await?;
-            if self.parser.1 {
-                self.writer
-                    .write((self.error_formatter)(e.to_string()).as_bytes())
-                    .await?;
-                self.writer.flush().await?;
-            }
-        }
-        result
+    /// Display `header` above the message, to keep the context of the question visible.
+    ///
+    /// In a terminal, the header is displayed again above every new attempt,
+    /// so it does not scroll away after failed attempts.
+    /// Otherwise, it is displayed only once.
+    pub fn sticky_header(mut self, header: impl ToString) -> Self {
+        self.config.sticky_header = Some(header.to_string());
+        self
     }
 
-    async fn test_proposal(&mut self, proposal: &T) -> eyre::Result<()> {
-        for test in &self.tests {
-            let result = (test.0)(proposal);
-            if let Err(e) = result {
-                if test.1 {
-                    self.writer
-                        .write((self.error_formatter)(e.to_string()).as_bytes())
-                        .await?;
-                    self.writer.flush().await?;
-                }
-                self.display_help().await?;
-                return Err(e);
-            }
+    fn header(&self, attempt: usize) -> String {
+        match &self.config.sticky_header {
+            Some(header) if attempt == 1 || self.config.terminal => self.indented(header),
+            _ => String::new(),
         }
-        Ok(())
     }
 
-    async fn display_help(&mut self) -> Result<(), std::io::Error> {
-        self.writer.write(self.help.0.as_bytes()).await?;
-        self.writer.flush().await?;
+    fn can_mask(&self) -> bool {
+        self.config.terminal && terminal::ModeGuard::is_supported()
+    }
 
-        if !self.help.1 {
-            self.help = ("".to_string(), false);
+    /// How the line is displayed while it is typed, if it is edited in a terminal.
+    fn echo(&self) -> Option<line_editor::Echo> {
+        match (
+            self.config.mask,
+            self.config.mask_length_indicator,
+            &self.config.live_preview,
+        ) {
+            _ if !self.can_mask() => None,
+            (true, true, _) => Some(line_editor::Echo::MaskedLength),
+            (false, _, Some(preview)) => Some(line_editor::Echo::Preview(preview.clone())),
+            (false, _, None) if self.config.prefill.is_some() => Some(line_editor::Echo::Plain),
+            _ => None,
         }
-        Ok(())
     }
 
-    async fn give_feedback(&mut self, value: &T) -> Result<(), std::io::Error> {
-        self.writer.write((self.feedback)(value).as_bytes()).await?;
-        self.writer.flush().await?;
-        Ok(())
+    /// Returns `true` if input is read by the line editor or `read_until_idle`,
+    /// which only read UTF-8.
+    fn reads_utf8_only(&self) -> bool {
+        self.echo().is_some() || matches!(self.config.executor, Executor::IdleTimeout(_))
     }
 }
 
-/// # Processing text input
-///
-/// ## Remarks
-///
-/// This is done after applying the preparser and before parsing the input.
-/// Therefore, the changes the preparser process (like triming trailing space)
-/// do not count for the length of the input.
-impl<T, R, W> QuestionBuilder<T, R, W> {
-    /// Set the preparser for the input.
+/// # Prompt functionalities
+impl<T, R, W> QuestionBuilder<T, R, W>
+where
+    W: Write + Unpin,
+    R: Read + Unpin,
+{
+    /// Asynchronously gets input from the user.
     ///
-    /// This is applied to the raw input before being parsed to `T`.
-    /// In CLI applications, it is useful to clean the leading new line that comes with the input.
-    pub fn preparser<F>(mut self, preparser: F) -> Self
-    where
-        F: Fn(String) -> String + Send + Sync + 'static,
-    {
-        self.preparser = Arc::new(preparser);
-        self
-    }
-
-    /// Add a test over the unparsed input.
+    /// The detailed process is as follows.
     ///
-    /// # Remarks
+    /// 0. Check there are [`attempts`] left
+    /// 1. Write [`message`]
+    /// 2. Write [`feedback from attempts`]
+    /// 3. Read input
+    /// 4. Apply the [`preparser`] to the input
+    /// 5. Return [`default_value`] if it corresponds
+    /// 6. Apply all [`str_test`]s
+    /// 4. Convert the input with [`parser`]
+    /// 5. Apply all [`test`]s
+    /// 6. Write [`feedback`]
+    /// 7. Return the value
     ///
-    /// There is a default message that you might want to change.
-    pub fn str_test<F>(self, str_test: F) -> Self
-    where
-        F: Fn(&str) -> bool + Send + Sync + 'static,
-    {
-        self.str_test_with_msg(str_test, "The input failed a test before being parsed.")
-    }
-
-    /// Add a test over the unparsed input, displaying a message upon failure.
-    pub fn str_test_with_msg<F, M>(self, str_test: F, message: M) -> Self
-    where
-        F: Fn(&str) -> bool + Send + Sync + 'static,
-        M: ToString + Send + Sync + 'static,
-    {
-        let str_test = move |s: &str| match str_test(s) {
-            true => Ok(()),
-            false => Err(Report::msg(message.to_string())),
-        };
-        self.str_test_with_feedback(str_test)
-    }
-
-    /// Tests that the input length is equal to `exact_length`.
+    /// [`attempts`]: #method.attempts
+    /// [`message`]: #method.message
+    /// [`feedback from attempts`]: #method.attempts_with_feedback
+    /// [`preparser`]: #method.preparser
+    /// [`default_value`]: #method.default_value
+    /// [`str_test`]: #method.str_test
+    /// [`parser`]: #method.parser
+    /// [`test`]: #method.test
+    /// [`feedback`]: #method.feedback
     ///
     /// # Remarks
     ///
-    /// There is a default message that you might want to change.
-    pub fn length(self, exact_length: usize) -> Self {
-        self.length_with_msg(
-            exact_length,
-            format!("The input needs to have length exactly {}.", exact_length),
-        )
+    /// There are two types of messages that can be displayed during the process:
+    /// feedback and error messages.
+    /// Feedback is displayed as given by the corresponding method.
+    /// Error messages are displayed after applying [`error_formatter`].
+    ///
+    /// More over, error messages are displayed through the [`eyre`] crate.
+    /// Therefore, you can further configure their display
+    /// (check out [`EyreHandler`]). [`error_formatter`]
+    /// is applied on top of displaying the error.
+    ///
+    /// # Allocations
+    ///
+    /// The value is always owned. For text, the input is read into a new `String`,
+    /// which preparsers and the parser may copy again. Moreover, building a question
+    /// allocates its settings. To ask many text values, reuse a single question
+    /// and buffer with [`ask_into_buffer`].
+    ///
+    /// [`error_formatter`]: #method.error_formatter
+    /// [`eyre`]: https://crates.io/crates/eyre
+    /// [`EyreHandler`]: https://docs.rs/eyre/0.6.5/eyre/trait.EyreHandler.html
+    /// [`ask_into_buffer`]: #method.ask_into_buffer
+    pub async fn ask(self) -> Result<T, ProcessingError> {
+        self.ask_with_raw().await.map(|(value, _)| value)
     }
 
-    /// Tests that the input length is equal to `exact_length`, displaying a message upon failure.
+    /// Asynchronously gets input from the user, with a single attempt.
     ///
-    pub fn length_with_msg<M>(self, exact_length: usize, message: M) -> Self
-    where
-        M: ToString + Send + Sync + 'static,
-    {
-        self.str_test_with_msg(move |s: &str| s.len() == exact_length, message)
+    /// Unlike setting [`attempts`] to one, an invalid input returns the error [`Rejected`],
+    /// describing whether it failed parsing or a test. Feedback is still given on success.
+    ///
+    /// [`attempts`]: #method.attempts
+    /// [`Rejected`]: error/enum.ProcessingError.html#variant.Rejected
+    pub async fn ask_once(mut self) -> Result<T, ProcessingError> {
+        self.once = true;
+        self.ask().await
     }
 
-    /// Tests that the input length is less or equal to `max_length`.
+    /// Asynchronously gets input from the user, together with the input that produced it.
+    ///
+    /// The input is returned after applying the [`preparser`].
+    /// For example, the input `"007\n"` could give the value `7` and the input `"007"`.
     ///
     /// # Remarks
     ///
-    /// There is a default message that you might want to change.
-    pub fn max_length(self, max_length: usize) -> Self {
-        self.length_with_msg(
-            max_length,
-            format!("The input needs to have length at most {}.", max_length),
-        )
+    /// If the [`default_value`] is returned, the input is empty.
+    ///
+    /// [`preparser`]: #method.preparser
+    /// [`default_value`]: #method.default_value
+    pub async fn ask_with_raw(mut self) -> Result<(T, String), ProcessingError> {
+        self.ask_executor().await
     }
 
-    /// Tests that the input length is less or equal to `max_length`, displaying a message upon failure.
-    pub fn max_length_with_msg<M>(self, max_length: usize, message: M) -> Self
-    where
-        M: ToString + Send + Sync + 'static,
-    {
-        self.str_test_with_msg(move |s: &str| s.len() <= max_length, message)
+    /// Asynchronously gets input from the user, and then gives back the reader.
+    ///
+    /// Together with the reader, it returns the bytes that were read but not consumed,
+    /// so that you can continue reading, for example, a protocol over a socket.
+    /// Check out [`into_reader`].
+    ///
+    /// [`into_reader`]: #method.into_reader
+    pub async fn ask_with_reader(mut self) -> (Result<T, ProcessingError>, (R, Vec<u8>)) {
+        let result = self.ask_executor().await.map(|(value, _)| value);
+        (result, self.into_reader())
     }
 
-    /// Tests that the input length is greater or equal to `min_length`.
+    /// Asynchronously gets values from the user, pushing them into `out`, until there is no more input.
     ///
-    /// # Remarks
+    /// Before each value, the question starts over: the message is displayed again
+    /// and the attempts, help and default value are restored.
     ///
-    /// There is a default message that you might want to change.
-    pub fn min_length(self, min_length: usize) -> Self {
-        self.length_with_msg(
-            min_length,
-            format!("The input needs to have length at least {}.", min_length),
-        )
+    /// # Stop conditions
+    ///
+    /// Asking stops, returning the number of values pushed, when either
+    /// - the reader gets to the end of file, or
+    /// - the line set by [`abort_keyword`] is read.
+    ///
+    /// Any other error is returned, keeping the values pushed so far in `out`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut numbers = Vec::new();
+    /// let pushed = async_std::task::block_on(
+    ///     asking::question::<u8>()
+    ///         .reader("1\n2\ndone\n3\n".as_bytes())
+    ///         .abort_keyword("done")
+    ///         .ask_into(&mut numbers),
+    /// )
+    /// .unwrap();
+    /// assert_eq!(pushed, 2);
+    /// assert_eq!(numbers, vec![1, 2]);
+    /// ```
+    ///
+    /// [`abort_keyword`]: #method.abort_keyword
+    pub async fn ask_into(mut self, out: &mut Vec<T>) -> Result<usize, ProcessingError>
+    where
+        T: Clone,
+    {
+        let restart = self.restart_point();
+        let mut pushed = 0;
+        loop {
+            match self.ask_executor().await {
+                Ok((value, _)) => out.push(value),
+                Err(ProcessingError::Eof) | Err(ProcessingError::Aborted) => return Ok(pushed),
+                Err(e) => return Err(e),
+            }
+            pushed += 1;
+            self.restart(&restart);
+        }
     }
 
-    /// Tests that the input length is greater or equal to `min_length`, displaying a message upon failure.
-    pub fn min_length_with_msg<M>(self, min_length: usize, message: M) -> Self
+    fn restart_point(&self) -> Restart<T>
     where
-        M: ToString + Send + Sync + 'static,
+        T: Clone,
     {
-        self.str_test_with_msg(move |s: &str| s.len() >= min_length, message)
+        Restart {
+            message: (self.config.message.clone(), self.config.repeat_message),
+            help: (self.config.help.clone(), self.config.repeat_help),
+            default: self.default.clone(),
+            attempts: self.config.attempts.as_ref().map(|(attempts, _)| *attempts),
+        }
     }
 
-    /// Set the parser for the input.
-    ///
-    /// # Remarks
-    ///
-    /// Errors will NOT be displayed if they occur.
-    /// Check out [`parser_with_feedback`] if you want to display a message upon failure.
-    ///
-    /// [`parser_with_feedback`]: #method.parser_with_feedback
-    pub fn parser<F>(mut self, parser: F) -> Self
+    /// Starts the question over, as it was at the `restart` point.
+    fn restart(&mut self, restart: &Restart<T>)
     where
-        F: Fn(&str) -> eyre::Result<T> + Send + Sync + 'static,
+        T: Clone,
     {
-        self.parser = (Arc::new(parser), false);
-        self
+        self.attempt = 0;
+        self.inputs_read = 0;
+        self.input_time = Duration::ZERO;
+        (self.config.message, self.config.repeat_message) = restart.message.clone();
+        (self.config.help, self.config.repeat_help) = restart.help.clone();
+        self.default = restart.default.clone();
+        if let (Some((left_attempts, _)), Some(attempts)) =
+            (&mut self.config.attempts, restart.attempts)
+        {
+            *left_attempts = attempts;
+        }
     }
-}
 
-/// # Advanced methods
-impl<T, R, W> QuestionBuilder<T, R, W> {
-    /// Change the reader.
-    pub fn reader<R2: Read>(self, other_reader: R2) -> QuestionBuilder<T, R2, W> {
-        QuestionBuilder {
-            reader: BufReader::new(other_reader),
-            writer: self.writer,
-            message: self.message,
-            help: self.help,
-            default: self.default,
-            feedback: self.feedback,
-            preparser: self.preparser,
-            str_tests: self.str_tests,
-            parser: self.parser,
-            tests: self.tests,
-            error_formatter: self.error_formatter,
-            executor: self.executor,
-            attempts: self.attempts,
-            required: self.required,
+    async fn ask_executor(&mut self) -> Result<(T, String), ProcessingError> {
+        let mut result = match self.config.cancel_on.take() {
+            Some(cancel) => self.ask_cancellable(cancel).await,
+            None => self.ask_timed().await,
+        };
+        if let (Err(e), Ok(_)) = (self.flush_output().await, &result) {
+            result = Err(e.into());
         }
+        self.write_outcome(&result).await;
+        result
     }
-    /// Change the writer.
-    pub fn writer<W2: Write>(self, other_writer: W2) -> QuestionBuilder<T, R, W2> {
-        QuestionBuilder {
-            reader: self.reader,
-            writer: BufWriter::new(other_writer),
-            message: self.message,
-            help: self.help,
-            default: self.default,
-            feedback: self.feedback,
-            preparser: self.preparser,
-            str_tests: self.str_tests,
-            parser: self.parser,
-            tests: self.tests,
-            error_formatter: self.error_formatter,
-            executor: self.executor,
-            attempts: self.attempts,
-            required: self.required,
+
+    async fn ask_cancellable(
+        &mut self,
+        mut cancel: Cancellation,
+    ) -> Result<(T, String), ProcessingError> {
+        let mut ask = Box::pin(self.ask_timed());
+        let result = std::future::poll_fn(|cx| match Pin::new(&mut cancel).poll(cx) {
+            Poll::Ready(()) => Poll::Ready(None),
+            Poll::Pending => ask.as_mut().poll(cx).map(Some),
+        })
+        .await;
+        drop(ask);
+        match result {
+            Some(result) => {
+                self.config.cancel_on = Some(cancel);
+                result
+            }
+            None => Err(ProcessingError::Cancelled),
         }
     }
 
-    /// Change the way errors are displayed.
-    ///
-    /// # Remarks
+    async fn flush_unbuffered(&mut self) -> Result<(), std::io::Error> {
+        match self.config.buffered {
+            true => Ok(()),
+            false => self.flush_output().await,
+        }
+    }
+
+    async fn flush_output(&mut self) -> Result<(), std::io::Error> {
+        let result = self.writer.flush().await;
+        self.ignoring_write_errors(result)
+    }
+
+    async fn write_output(&mut self, bytes: &[u8]) -> Result<(), std::io::Error> {
+        let result = self.writer.write_all(bytes).await;
+        self.ignoring_write_errors(result)
+    }
+
+    fn ignoring_write_errors(&self, result: std::io::Result<()>) -> std::io::Result<()> {
+        match result {
+            Err(_) if self.config.ignore_write_errors => Ok(()),
+            result => result,
+        }
+    }
+
+    async fn write_outcome(&mut self, result: &Result<(T, String), ProcessingError>) {
+        let (writer, format) = match &mut self.outcome_log {
+            Some(outcome_log) => outcome_log,
+            None => return,
+        };
+        let line = match result {
+            Ok((value, _)) => format!(
+                "outcome=success attempts={} value={}\n",
+                self.inputs_read,
+                format(value)
+            ),
+            Err(e) => format!(
+                "outcome=failure attempts={} error={:?}\n",
+                self.inputs_read,
+                e.to_string()
+            ),
+        };
+        if writer.write_all(line.as_bytes()).await.is_ok() {
+            writer.flush().await.ok();
+        }
+    }
+
+    async fn ask_timed(&mut self) -> Result<(T, String), ProcessingError> {
+        match self.config.executor {
+            Executor::None | Executor::TimeoutInputOnly(_) | Executor::IdleTimeout(_) => {
+                self.ask_loop().await
+            }
+            Executor::Timeout(duration) => async_std::future::timeout(duration, self.ask_loop())
+                .await
+                .map_err(|source| ProcessingError::Timeout { source, duration })?,
+        }
+    }
+
+    /// Synchronously gets input from the user.
     ///
-    /// This can be useful if to:
-    /// - Change the default behaviour of appending a newline.
-    /// - Not display a message upon an error.
+    /// Convenience method for `async_std::task::block_on(self.ask())`.
     ///
-    /// # Examples
+    /// # Remarks
     ///
-    /// Silent errors. Now, only messages will be displayed.
-    /// ```no_run
-    /// # #[async_std::main]
-    /// # async fn main() -> eyre::Result<()> {
-    ///     let _num = asking::question()
-    ///         .message("Give me a number bigger than 3, please. I will not tell you anything else.\n")
-    ///         .test(|i: &u32| *i > 3)
-    ///         .error_formatter(|_| "".to_string())
-    ///         .ask()
-    ///         .await?;
-    /// #     Ok(())
-    /// # }
-    /// ```
-    pub fn error_formatter<F>(mut self, error_formatter: F) -> Self
-    where
-        F: Fn(String) -> String + Send + Sync + 'static,
-    {
-        self.error_formatter = Arc::new(error_formatter);
-        self
+    /// Although this method goes against the goal of this crate,
+    /// it is useful for quick implementations.
+    pub fn ask_and_wait(self) -> Result<T, ProcessingError> {
+        async_std::task::block_on(self.ask())
     }
 
-    /// Toggle the feedback from the parser.
-    ///
-    /// If activated, errors from parsing will be displayed.
-    pub fn parser_feedback_toggle(mut self) -> Self {
-        self.parser.1 = !self.parser.1;
-        self
+    async fn ask_loop(&mut self) -> Result<(T, String), ProcessingError> {
+        self.asked_at = Instant::now();
+        let on_timing = self.config.on_timing.clone();
+        let result = self.ask_attempts().await;
+        if let (Ok(_), Some(on_timing)) = (&result, on_timing) {
+            on_timing(self.asked_at.elapsed());
+        }
+        result
     }
 
-    /// Set the parser for the input.
-    ///
-    /// Errors will be displayed if they occur.
-    pub fn parser_with_feedback<F>(mut self, parser: F) -> Self
-    where
-        F: Fn(&str) -> eyre::Result<T> + Send + Sync + 'static,
-    {
-        self.parser = (Arc::new(parser), true);
-        self
+    async fn ask_attempts(&mut self) -> Result<(T, String), ProcessingError> {
+        if let Some(path) = self.config.secret_from_path.clone() {
+            return self.read_secret(path).await;
+        }
+        if let Some(chain) = &self.default_chain {
+            if let Some(default) = chain(self.parser.0.clone()).await {
+                self.default = Some(default);
+            }
+        }
+        if self.config.dry_run {
+            return self.answer_dry_run().await;
+        }
+        if self.config.mask && self.config.require_tty_for_mask && !self.can_mask() {
+            return Err(ProcessingError::MaskUnavailable);
+        }
+        if let Some(required_if) = &self.config.required_if {
+            self.config.required = required_if(&self.config.context);
+        }
+        let initial_attempts = self.config.attempts.as_ref().map(|(attempts, _)| *attempts);
+        let mut previous_input = None;
+        loop {
+            if let Err(e) = self.check_attempts() {
+                return self.fallback_default(e);
+            }
+            if self.attempt > 0 && !self.config.retry_delay.is_zero() {
+                async_std::task::sleep(self.config.retry_delay).await;
+            }
+            if let (true, Some(previous_input)) = (self.config.show_previous_input, &previous_input)
+            {
+                let reminder = self.indented(&format!("(you entered: {})\n", previous_input));
+                self.write_output(reminder.as_bytes()).await?;
+            }
+            self.write_message().await?;
+            self.write_attempts_feedback().await?;
+            let preinput = self.take_input().await?;
+            self.inputs_read += 1;
+            self.decrease_attempts()?;
+            let preinput = match &self.config.transform_input {
+                Some(transform) => transform(preinput).await,
+                None => preinput,
+            };
+            let input = self.preparse(preinput);
+            previous_input = Some(input.clone());
+            if (self.config.is_empty_when)(&input) && !self.config.required {
+                if let Some(default) = self.default.take() {
+                    return Ok((default, input));
+                }
+            }
+            if let Err(e) = self.test_string(&input).await {
+                self.check_once(e)?;
+                continue;
+            };
+            let proposal = match self.parse_input(&input).await {
+                Ok(value) => value,
+                Err(e) if self.config.parse_error_uses_default && !self.config.required => {
+                    match self.default.take() {
+                        Some(default) => return Ok((default, input)),
+                        None => {
+                            self.check_once(e)?;
+                            continue;
+                        }
+                    }
+                }
+                Err(e) => {
+                    self.check_once(e)?;
+                    continue;
+                }
+            };
+            if let Err(e) = self.test_proposal(&proposal).await {
+                self.reset_attempts(&proposal, initial_attempts);
+                self.check_once(e)?;
+                continue;
+            }
+            let proposal = self.rounded(proposal);
+            self.check_confirmation(&input).await?;
+            let proposal = self.check_double_confirm(proposal).await?;
+            if !self.check_confirm_value(&proposal).await? {
+                self.check_once(Report::msg("the value was not confirmed"))?;
+                continue;
+            }
+            self.give_feedback(&proposal).await?;
+
+            return Ok((proposal, input));
+        }
     }
 
-    /// Add a test over the unparsed input.
-    ///
-    /// Errors will be displayed if they occur.
-    pub fn str_test_with_feedback<F>(mut self, str_test: F) -> Self
-    where
-        F: Fn(&str) -> eyre::Result<()> + Send + Sync + 'static,
-    {
-        self.str_tests.push((Arc::new(str_test), true));
-        self
+    async fn read_secret(&self, path: PathBuf) -> Result<(T, String), ProcessingError> {
+        let secret = match async_std::fs::read_to_string(&path).await {
+            Ok(secret) => secret.trim().to_string(),
+            Err(source) => return Err(ProcessingError::SecretUnreadable { path, source }),
+        };
+        match self.validate(&secret) {
+            Ok(value) => Ok((value, secret)),
+            Err(e) => Err(ProcessingError::InvalidSecret {
+                path,
+                reason: e.root_cause().to_string(),
+            }),
+        }
     }
 
-    /// Add a test over the parsed input
-    ///
-    /// Errors will be displayed if they occur.
-    pub fn test_with_feedback<F>(mut self, test: F) -> Self
-    where
-        F: Fn(&T) -> eyre::Result<()> + Send + Sync + 'static,
-    {
-        self.tests.push((Arc::new(test), true));
-        self
+    async fn answer_dry_run(&mut self) -> Result<(T, String), ProcessingError> {
+        if !self.config.message_lazy {
+            self.write_message().await?;
+        }
+        let default = self.default.take().ok_or(ProcessingError::NoDefault)?;
+        self.give_feedback(&default).await?;
+        Ok((default, String::new()))
     }
-}
 
-impl<T, R, W> Debug for QuestionBuilder<T, R, W>
-where
-    T: Debug,
-    R: Read + Debug,
-    W: Write + Debug,
-{
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("QuestionBuilder")
-            .field("reader", &self.reader)
-            .field("writer", &self.writer)
-            .field("message", &self.message)
-            .field("help", &self.help)
-            .field("default", &self.default)
-            .field("executor", &self.executor)
-            .field("required", &self.required)
-            .finish_non_exhaustive()
+    /// Returns the error `Rejected` if only one attempt is allowed, as in `ask_once`.
+    fn check_once(&self, error: Report) -> Result<(), ProcessingError> {
+        match self.once {
+            true => Err(ProcessingError::Rejected {
+                reason: format!("{:#}", error),
+            }),
+            false => Ok(()),
+        }
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use async_std::io;
+    fn check_attempts(&mut self) -> Result<(), ProcessingError> {
+        match self.config.attempts {
+            Some((0, _)) => Err(ProcessingError::NoMoreAttempts),
+            _ => Ok(()),
+        }
+    }
 
-    #[test]
-    fn is_send() {
-        if false {
-            let question = QuestionBuilder::new_fromstr(io::stdin(), io::stdout()).ask();
-            fn is_send<T: Send>(_: &T) {}
-            is_send(&question);
-            let _answer: bool = async_std::task::block_on(question).unwrap();
+    fn fallback_default(&mut self, error: ProcessingError) -> Result<(T, String), ProcessingError> {
+        match (
+            &error,
+            self.config.attempts_then_default,
+            self.default.take(),
+        ) {
+            (ProcessingError::NoMoreAttempts, true, Some(default)) => Ok((default, String::new())),
+            _ => Err(error),
+        }
+    }
+
+    async fn write_message(&mut self) -> Result<(), std::io::Error> {
+        self.attempt += 1;
+        let message =
+            self.header(self.attempt) + &self.indented(&self.affixed_message(self.attempt));
+        self.write_output(message.as_bytes()).await?;
+        self.flush_unbuffered().await?;
+        if !self.config.repeat_message {
+            self.config.message = Arc::new(|_| String::default());
+        }
+        Ok(())
+    }
+
+    async fn write_attempts_feedback(&mut self) -> Result<(), std::io::Error> {
+        if let Some((left_attempts, feedback)) = &self.config.attempts {
+            let feedback = feedback(*left_attempts);
+            self.write_output(feedback.as_bytes()).await?;
+            self.flush_unbuffered().await?;
+        };
+        Ok(())
+    }
+
+    async fn take_input(&mut self) -> Result<String, ProcessingError> {
+        let token = match self.config.terminator_line.clone() {
+            Some(token) => token,
+            None => return self.take_unaborted_line().await,
+        };
+        let mut input = String::new();
+        loop {
+            let line = match self.take_unaborted_line().await {
+                Ok(line) => line,
+                Err(ProcessingError::Eof) if !input.is_empty() => return Ok(input),
+                Err(e) => return Err(e),
+            };
+            if line.trim_end_matches(&['\n', '\r'][..]) == token {
+                return Ok(input);
+            }
+            input += &line;
         }
     }
+
+    async fn take_unaborted_line(&mut self) -> Result<String, ProcessingError> {
+        let line = self.take_line().await?;
+        match &self.config.abort_keyword {
+            Some(keyword) if line.trim_end_matches(&['\n', '\r'][..]) == keyword => {
+                Err(ProcessingError::Aborted)
+            }
+            _ => Ok(line),
+        }
+    }
+
+    async fn take_line(&mut self) -> Result<String, ProcessingError> {
+        self.flush_output().await?;
+        let mut bytes = Vec::new();
+        let terminator = self.config.line_terminator;
+        if self.reads_utf8_only() && self.config.input_encoding != Encoding::Utf8 {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "input can only be read as UTF-8 with idle_timeout or a line editor",
+            )
+            .into());
+        }
+        let echo = self.echo();
+        let prefill = match self.config.mask {
+            true => String::new(),
+            false => self.config.prefill.clone().unwrap_or_default(),
+        };
+        // Only the terminal of the standard input can change its mode.
+        let _mode_guard = match (self.config.mask && self.can_mask(), &echo) {
+            _ if !self.config.reads_stdin => None,
+            (_, Some(_)) => match terminal::ModeGuard::raw() {
+                Ok(guard) => Some(guard),
+                Err(e) if self.config.mask && std::io::stdin().is_terminal() => {
+                    return Err(e.into())
+                }
+                Err(_) => None,
+            },
+            (true, None) => Some(terminal::ModeGuard::no_echo()?),
+            (false, None) => None,
+        };
+        let encoding = self.config.input_encoding;
+        let result = match (self.config.executor.clone(), echo) {
+            (Executor::TimeoutInputOnly(duration), Some(echo)) => {
+                let start = Instant::now();
+                let (reader, writer) = (&mut self.reader, &mut self.writer);
+                let read = line_editor::read_line(
+                    reader, writer, terminator, &mut bytes, &echo, &prefill, None,
+                );
+                let remaining = duration.saturating_sub(self.input_time);
+                let result = async_std::future::timeout(remaining, read)
+                    .await
+                    .map_err(|source| ProcessingError::Timeout { source, duration })?
+                    .map_err(|source| ProcessingError::Timeout { source, duration })?;
+                self.input_time += start.elapsed();
+                result
+            }
+            (Executor::TimeoutInputOnly(duration), None) => {
+                let start = Instant::now();
+                let read = encoding.read_until(&mut self.reader, terminator, &mut bytes);
+                let remaining = duration.saturating_sub(self.input_time);
+                let result = async_std::future::timeout(remaining, read)
+                    .await
+                    .map_err(|source| ProcessingError::Timeout { source, duration })?;
+                self.input_time += start.elapsed();
+                result
+            }
+            (executor, Some(echo)) => {
+                let idle = match executor {
+                    Executor::IdleTimeout(idle) => Some(idle),
+                    _ => None,
+                };
+                let (reader, writer) = (&mut self.reader, &mut self.writer);
+                line_editor::read_line(
+                    reader, writer, terminator, &mut bytes, &echo, &prefill, idle,
+                )
+                .await
+                .map_err(|source| ProcessingError::Timeout {
+                    source,
+                    duration: idle.unwrap_or_default(),
+                })?
+            }
+            (Executor::IdleTimeout(duration), None) => {
+                read_until_idle(&mut self.reader, terminator, &mut bytes, duration)
+                    .await
+                    .map_err(|source| ProcessingError::Timeout { source, duration })?
+            }
+            (_, None) => {
+                encoding
+                    .read_until(&mut self.reader, terminator, &mut bytes)
+                    .await
+            }
+        };
+        match result {
+            Ok(0) => Err(ProcessingError::Eof),
+            Ok(read) => {
+                self.session_used += read;
+                if let Some(limit) = self.config.session_budget {
+                    if self.session_used > limit {
+                        return Err(ProcessingError::InputTooLong { limit });
+                    }
+                }
+                if terminator != b'\n' {
+                    encoding.strip_terminator(&mut bytes, terminator);
+                }
+                Ok(encoding.decode(bytes)?)
+            }
+            Err(e) => match e.kind() {
+                std::io::ErrorKind::BrokenPipe
+                | std::io::ErrorKind::ConnectionReset
+                | std::io::ErrorKind::ConnectionAborted => {
+                    Err(ProcessingError::Disconnected { source: e })
+                }
+                _ => Err(e.into()),
+            },
+        }
+    }
+
+    async fn check_confirmation(&mut self, input: &str) -> Result<(), ProcessingError> {
+        let (message, mismatch) = match self.config.confirmation.clone() {
+            Some(confirmation) => confirmation,
+            None => return Ok(()),
+        };
+        for _ in 0..=self.config.confirm_retries {
+            self.write_output(self.indented(&message).as_bytes())
+                .await?;
+            self.flush_unbuffered().await?;
+            let preconfirmation = self.take_input().await?;
+            let confirmation = self.preparse(preconfirmation);
+            if confirmation == input {
+                return Ok(());
+            }
+            self.write_output((self.config.error_formatter)(mismatch.clone()).as_bytes())
+                .await?;
+            self.flush_unbuffered().await?;
+        }
+        Err(ProcessingError::ConfirmationMismatch)
+    }
+
+    async fn check_double_confirm(&mut self, proposal: T) -> Result<T, ProcessingError> {
+        let (message, cancelled) = match &self.double_confirm {
+            Some((message, cancel)) => match cancel(&proposal) {
+                Some(cancelled) => (message.clone(), cancelled),
+                None => return Ok(proposal),
+            },
+            None => return Ok(proposal),
+        };
+        match self.ask_yn(&message).await? {
+            true => Ok(proposal),
+            false => Ok(cancelled),
+        }
+    }
+
+    async fn check_confirm_value(&mut self, proposal: &T) -> Result<bool, ProcessingError> {
+        let message = match self
+            .confirm_value
+            .as_ref()
+            .and_then(|template| template(proposal))
+        {
+            Some(message) => message,
+            None => return Ok(true),
+        };
+        self.ask_yn(&message).await
+    }
+
+    /// Asks a yes/no question, displaying `message`, until it gets a valid answer.
+    async fn ask_yn(&mut self, message: &str) -> Result<bool, ProcessingError> {
+        loop {
+            self.write_output(self.indented(message).as_bytes()).await?;
+            self.flush_unbuffered().await?;
+            let preanswer = self.take_line().await?;
+            if let Ok(answer) = crate::pattern::parse_yn(&self.preparse(preanswer)) {
+                return Ok(answer);
+            }
+        }
+    }
+
+    fn reset_attempts(&mut self, proposal: &T, initial_attempts: Option<usize>) {
+        if let (Some(predicate), Some((left_attempts, _)), Some(initial)) = (
+            &self.reset_attempts_on,
+            &mut self.config.attempts,
+            initial_attempts,
+        ) {
+            if predicate(proposal) {
+                *left_attempts = initial;
+            }
+        }
+    }
+
+    fn decrease_attempts(&mut self) -> Result<(), ProcessingError> {
+        if let Some((left_attempts, _)) = &mut self.config.attempts {
+            *left_attempts -= 1;
+        }
+        Ok(())
+    }
+
+    async fn test_string(&mut self, str_proposal: &str) -> eyre::Result<()> {
+        let mut result = Ok(());
+        for str_test in self.config.str_tests.clone() {
+            if let Err(e) = (str_test.0)(str_proposal) {
+                if str_test.1 {
+                    self.write_output((self.config.error_formatter)(e.to_string()).as_bytes())
+                        .await?;
+                    self.flush_unbuffered().await?;
+                }
+                if result.is_ok() {
+                    result = Err(e);
+                }
+                if self.config.str_test_mode == TestMode::FirstFailure {
+                    break;
+                }
+            }
+        }
+        if result.is_err() {
+            self.display_help().await?;
+        }
+        result.wrap_err("while testing the input")
+    }
+
+    async fn parse_input(&mut self, input: &str) -> eyre::Result<T> {
+        if (self.config.is_empty_when)(input) && self.config.required {
+            if self.config.required_help == Some(true) {
+                self.display_help().await?;
+            }
+            self.write_output(
+                (self.config.error_formatter)(self.config.required_message.clone()).as_bytes(),
+            )
+            .await?;
+            self.flush_unbuffered().await?;
+            if self.config.required_help == Some(false) {
+                self.display_help().await?;
+            }
+            return Err(Report::msg(self.config.required_message.clone()))
+                .wrap_err("while parsing the input");
+        }
+        let result = (self.parser.0)(input);
+        if let Err(ref e) = result {
+            self.display_help().await?;
+            if self.parser.1 {
+                self.write_output((self.config.error_formatter)(e.to_string()).as_bytes())
+                    .await?;
+                self.flush_unbuffered().await?;
+            }
+        }
+        result.wrap_err("while parsing the input")
+    }
+
+    async fn test_proposal(&mut self, proposal: &T) -> eyre::Result<()> {
+        if self.is_accepted(proposal) {
+            return Ok(());
+        }
+        for test in self.tests.clone() {
+            let result = (test.0)(proposal);
+            if let Err(e) = result {
+                if test.1 {
+                    self.write_output((self.config.error_formatter)(e.to_string()).as_bytes())
+                        .await?;
+                    self.flush_unbuffered().await?;
+                }
+                self.display_help().await?;
+                return Err(e.wrap_err("while validating the value"));
+            }
+        }
+        for test in self.async_tests.clone() {
+            if let Err(e) = test(proposal, Progress::new(self.config.progress.clone())).await {
+                self.write_output((self.config.error_formatter)(e.to_string()).as_bytes())
+                    .await?;
+                self.flush_unbuffered().await?;
+                self.display_help().await?;
+                return Err(e.wrap_err("while validating the value"));
+            }
+        }
+        Ok(())
+    }
+
+    async fn display_help(&mut self) -> Result<(), std::io::Error> {
+        if self.attempt <= self.config.help_after {
+            return Ok(());
+        }
+        let help = self.indented(&self.config.help);
+        self.write_output(help.as_bytes()).await?;
+        self.flush_unbuffered().await?;
+
+        if !self.config.repeat_help {
+            self.config.help = String::default();
+        }
+        Ok(())
+    }
+
+    async fn give_feedback(&mut self, value: &T) -> Result<(), std::io::Error> {
+        let mut feedback = match &self.timed_feedback {
+            Some(feedback) => feedback(value, self.asked_at.elapsed()),
+            None => (self.feedback)(value),
+        };
+        if self.config.feedback_newline && !feedback.is_empty() {
+            feedback.push('\n');
+        }
+        if let Some((text, url)) = &self.config.feedback_link {
+            feedback += &self.hyperlink(text, url);
+            feedback.push('\n');
+        }
+        let feedback = self.indented(&feedback);
+        for chunk in feedback.as_bytes().chunks(self.config.feedback_chunk_size) {
+            self.write_output(chunk).await?;
+            self.flush_unbuffered().await?;
+        }
+        Ok(())
+    }
+}
+
+/// State of a question that changes while asking.
+struct Restart<T> {
+    message: (Message, bool),
+    help: (String, bool),
+    default: Option<T>,
+    attempts: Option<usize>,
+}
+
+/// Replaces every `{name}` in `template` by the variable `name`, if there is one.
+fn render_template(template: &str, vars: &HashMap<String, String>) -> String {
+    if vars.is_empty() {
+        return template.to_string();
+    }
+    let mut rendered = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        rendered.push_str(&rest[..start]);
+        let placeholder = &rest[start..];
+        match placeholder
+            .find('}')
+            .and_then(|end| Some((vars.get(&placeholder[1..end])?, end)))
+        {
+            Some((value, end)) => {
+                rendered.push_str(value);
+                rest = &placeholder[end + 1..];
+            }
+            None => {
+                rendered.push('{');
+                rest = &placeholder[1..];
+            }
+        }
+    }
+    rendered.push_str(rest);
+    rendered
+}
+
+/// Reads until `terminator`, like `read_until`, but times out if no input is read for `idle` time.
+async fn read_until_idle<R: Read + Unpin>(
+    reader: &mut BufReader<R>,
+    terminator: u8,
+    bytes: &mut Vec<u8>,
+    idle: Duration,
+) -> Result<std::io::Result<usize>, async_std::future::TimeoutError> {
+    let start = bytes.len();
+    loop {
+        let chunk = std::future::poll_fn(|cx| {
+            let buffer = match Pin::new(&mut *reader).poll_fill_buf(cx) {
+                Poll::Ready(Ok(buffer)) => buffer,
+                Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+                Poll::Pending => return Poll::Pending,
+            };
+            let length = match buffer.iter().position(|&byte| byte == terminator) {
+                Some(index) => index + 1,
+                None => buffer.len(),
+            };
+            let chunk = buffer[..length].to_vec();
+            Pin::new(&mut *reader).consume(length);
+            Poll::Ready(Ok(chunk))
+        });
+        let chunk = match async_std::future::timeout(idle, chunk).await? {
+            Ok(chunk) => chunk,
+            Err(e) => return Ok(Err(e)),
+        };
+        bytes.extend_from_slice(&chunk);
+        if chunk.is_empty() || chunk.ends_with(&[terminator]) {
+            return Ok(Ok(bytes.len() - start));
+        }
+    }
+}
+
+/// # Processing text input
+///
+/// ## Remarks
+///
+/// This is done after applying the preparser and before parsing the input.
+/// Therefore, the changes the preparser process (like triming trailing space)
+/// do not count for the length of the input.
+impl<T, R, W> QuestionBuilder<T, R, W> {
+    /// Set the byte that ends a line of input.
+    ///
+    /// The default value is `b'\n'`, which is kept at the end of the input for the [`preparser`].
+    /// Any other terminator is removed from the input before the [`preparser`] is applied.
+    ///
+    /// [`preparser`]: #method.preparser
+    pub fn line_terminator(mut self, terminator: u8) -> Self {
+        self.config.line_terminator = terminator;
+        self
+    }
+
+    /// Set the encoding of the input, which is decoded to UTF-8 before the [`preparser`].
+    ///
+    /// The default is [`Encoding::Utf8`]. A leading byte order mark is removed.
+    /// For UTF-16, the [`line_terminator`] is read as a whole code unit.
+    ///
+    /// # Remarks
+    ///
+    /// Input can only be read as UTF-8 with [`idle_timeout`], and when it is edited in a terminal,
+    /// as with [`mask_length_indicator`], [`live_preview`] or [`prefill`].
+    /// Other encodings are rejected by [`validate_config`] and, when asking, with an I/O error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use asking::Encoding;
+    ///
+    /// let input: Vec<u8> = "yes\n".encode_utf16().flat_map(u16::to_le_bytes).collect();
+    /// let answer = asking::yn()
+    ///     .reader(input.as_slice())
+    ///     .input_encoding(Encoding::Utf16Le)
+    ///     .ask_and_wait();
+    /// assert_eq!(answer.unwrap(), true);
+    /// ```
+    ///
+    /// [`preparser`]: #method.preparser
+    /// [`Encoding::Utf8`]: enum.Encoding.html#variant.Utf8
+    /// [`line_terminator`]: #method.line_terminator
+    /// [`idle_timeout`]: #method.idle_timeout
+    /// [`mask_length_indicator`]: #method.mask_length_indicator
+    /// [`live_preview`]: #method.live_preview
+    /// [`prefill`]: #method.prefill
+    /// [`validate_config`]: #method.validate_config
+    pub fn input_encoding(mut self, encoding: Encoding) -> Self {
+        self.config.input_encoding = encoding;
+        self
+    }
+
+    /// Read several lines as input, until a line equal to `token`.
+    ///
+    /// The line with the `token` is not part of the input.
+    /// If the end of file is reached before the `token`, the lines read so far are the input.
+    pub fn terminator_line(mut self, token: impl ToString) -> Self {
+        self.config.terminator_line = Some(token.to_string());
+        self
+    }
+
+    /// Stop asking when the line `keyword` is read, returning the error [`Aborted`].
+    ///
+    /// This is the way to stop [`ask_into`] before the end of file.
+    ///
+    /// [`Aborted`]: error/enum.ProcessingError.html#variant.Aborted
+    /// [`ask_into`]: #method.ask_into
+    pub fn abort_keyword(mut self, keyword: impl ToString) -> Self {
+        self.config.abort_keyword = Some(keyword.to_string());
+        self
+    }
+
+    /// Set an asynchronous transformation of the raw input, applied before the [`preparser`].
+    ///
+    /// Useful for slow transformations, like looking up a short code in a remote table.
+    ///
+    /// [`preparser`]: #method.preparser
+    pub fn transform_input_async<F, Fut>(mut self, transform: F) -> Self
+    where
+        F: Fn(String) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = String> + Send + 'static,
+    {
+        self.config.transform_input = Some(Arc::new(move |input| Box::pin(transform(input))));
+        self
+    }
+
+    /// Set the preparser for the input.
+    ///
+    /// This is applied to the raw input before being parsed to `T`.
+    /// In CLI applications, it is useful to clean the leading new line that comes with the input.
+    ///
+    /// # Remarks
+    ///
+    /// This replaces all preparsers, including the default one that trims the end of the input.
+    pub fn preparser<F>(mut self, preparser: F) -> Self
+    where
+        F: Fn(String) -> String + Send + Sync + 'static,
+    {
+        self.config.preparsers = vec![Arc::new(preparser)];
+        self
+    }
+
+    /// Add a preparser for the input, applied after the previous ones.
+    ///
+    /// The first preparser, by default, trims the end of the input.
+    /// Check out [`clear_preparsers`] to remove it.
+    ///
+    /// [`clear_preparsers`]: #method.clear_preparsers
+    pub fn add_preparser<F>(mut self, preparser: F) -> Self
+    where
+        F: Fn(String) -> String + Send + Sync + 'static,
+    {
+        self.config.preparsers.push(Arc::new(preparser));
+        self
+    }
+
+    /// Set the preparser to only remove the line ending, keeping all other whitespace.
+    ///
+    /// Useful for formats where whitespace is significant, like indented code.
+    /// For example, the input `"  indented  \n"` is taken as `"  indented  "`.
+    ///
+    /// # Remarks
+    ///
+    /// This replaces all preparsers, as [`preparser`] does.
+    ///
+    /// [`preparser`]: #method.preparser
+    pub fn keep_leading_whitespace(self) -> Self {
+        self.preparser(|s| s.trim_end_matches(&['\n', '\r'][..]).to_string())
+    }
+
+    /// Forgets all preparsers, so the input is taken as it is read.
+    pub fn clear_preparsers(mut self) -> Self {
+        self.config.preparsers = vec![];
+        self
+    }
+
+    fn preparse(&self, input: String) -> String {
+        self.config
+            .preparsers
+            .iter()
+            .fold(input, |input, preparser| preparser(input))
+    }
+
+    /// Add a test over the unparsed input.
+    ///
+    /// # Remarks
+    ///
+    /// There is a default message that you might want to change.
+    pub fn str_test<F>(self, str_test: F) -> Self
+    where
+        F: Fn(&str) -> bool + Send + Sync + 'static,
+    {
+        self.str_test_with_msg(str_test, "The input failed a test before being parsed.")
+    }
+
+    /// Add a test over the unparsed input, displaying a message upon failure.
+    pub fn str_test_with_msg<F, M>(self, str_test: F, message: M) -> Self
+    where
+        F: Fn(&str) -> bool + Send + Sync + 'static,
+        M: ToString + Send + Sync + 'static,
+    {
+        let str_test = move |s: &str| match str_test(s) {
+            true => Ok(()),
+            false => Err(Report::msg(message.to_string())),
+        };
+        self.str_test_with_feedback(str_test)
+    }
+
+    /// Set how many failing tests over the unparsed input are reported.
+    ///
+    /// The default value is [`TestMode::FirstFailure`].
+    ///
+    /// [`TestMode::FirstFailure`]: enum.TestMode.html#variant.FirstFailure
+    pub fn str_test_mode(mut self, mode: TestMode) -> Self {
+        self.config.str_test_mode = mode;
+        self
+    }
+
+    /// Tests that the input length is equal to `exact_length`.
+    ///
+    /// # Remarks
+    ///
+    /// There is a default message that you might want to change.
+    pub fn length(self, exact_length: usize) -> Self {
+        self.length_with_msg(
+            exact_length,
+            format!("The input needs to have length exactly {}.", exact_length),
+        )
+    }
+
+    /// Tests that the input length is equal to `exact_length`, displaying a message upon failure.
+    ///
+    pub fn length_with_msg<M>(mut self, exact_length: usize, message: M) -> Self
+    where
+        M: ToString + Send + Sync + 'static,
+    {
+        self.config.length_bounds = (Some(exact_length), Some(exact_length));
+        self.str_test_with_msg(move |s: &str| s.len() == exact_length, message)
+    }
+
+    /// Tests that the input length is less or equal to `max_length`.
+    ///
+    /// # Remarks
+    ///
+    /// There is a default message that you might want to change.
+    pub fn max_length(self, max_length: usize) -> Self {
+        self.max_length_with_msg(
+            max_length,
+            format!("The input needs to have length at most {}.", max_length),
+        )
+    }
+
+    /// Tests that the input length is less or equal to `max_length`, displaying a message upon failure.
+    pub fn max_length_with_msg<M>(mut self, max_length: usize, message: M) -> Self
+    where
+        M: ToString + Send + Sync + 'static,
+    {
+        self.config.length_bounds.1 = Some(max_length);
+        self.str_test_with_msg(move |s: &str| s.len() <= max_length, message)
+    }
+
+    /// Tests that the input length is greater or equal to `min_length`.
+    ///
+    /// # Remarks
+    ///
+    /// There is a default message that you might want to change.
+    pub fn min_length(self, min_length: usize) -> Self {
+        self.min_length_with_msg(
+            min_length,
+            format!("The input needs to have length at least {}.", min_length),
+        )
+    }
+
+    /// Tests that the input length is greater or equal to `min_length`, displaying a message upon failure.
+    pub fn min_length_with_msg<M>(mut self, min_length: usize, message: M) -> Self
+    where
+        M: ToString + Send + Sync + 'static,
+    {
+        self.config.length_bounds.0 = Some(min_length);
+        self.str_test_with_msg(move |s: &str| s.len() >= min_length, message)
+    }
+
+    /// Tests that the input follows a password policy, with a message for each criterion.
+    ///
+    /// The input needs at least `min_length` characters and, if required,
+    /// an uppercase letter, a digit and a symbol (neither a letter, a digit nor whitespace).
+    ///
+    /// # Remarks
+    ///
+    /// To display all unmet criteria at once, use [`str_test_mode`] with [`TestMode::AllFailures`].
+    ///
+    /// [`str_test_mode`]: #method.str_test_mode
+    /// [`TestMode::AllFailures`]: enum.TestMode.html#variant.AllFailures
+    pub fn password_policy(
+        self,
+        min_length: usize,
+        require_upper: bool,
+        require_digit: bool,
+        require_symbol: bool,
+    ) -> Self {
+        let mut question = self.str_test_with_msg(
+            move |s: &str| s.chars().count() >= min_length,
+            format!("The password needs at least {} characters.", min_length),
+        );
+        if require_upper {
+            question = question.str_test_with_msg(
+                |s: &str| s.chars().any(char::is_uppercase),
+                "The password needs an uppercase letter.",
+            );
+        }
+        if require_digit {
+            question = question.str_test_with_msg(
+                |s: &str| s.chars().any(|c| c.is_ascii_digit()),
+                "The password needs a digit.",
+            );
+        }
+        if require_symbol {
+            question = question.str_test_with_msg(
+                |s: &str| {
+                    s.chars()
+                        .any(|c| !c.is_alphanumeric() && !c.is_whitespace())
+                },
+                "The password needs a symbol.",
+            );
+        }
+        question
+    }
+
+    /// Tests that the `hasher` maps the input to the `expected` digest.
+    ///
+    /// Useful for confirmation codes, like "enter the code we sent you",
+    /// where only a hash of the code is kept. Any hash function works,
+    /// for example, SHA-256 from the `sha2` crate.
+    ///
+    /// # Remarks
+    ///
+    /// There is a default message that you might want to change.
+    /// It does not reveal how the input differs from the code.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn sha256_hex(_: &str) -> String { unimplemented!() }
+    /// let expected = "5994471abb01112afcc18159f6cc74b4f511b99806da59b3caf5a9c173cacfc5".to_string();
+    /// let _code = asking::text()
+    ///     .message("Enter the code we sent you: ")
+    ///     .matches_hash(expected, sha256_hex)
+    ///     .ask_and_wait();
+    /// ```
+    pub fn matches_hash<D, H>(self, expected: D, hasher: H) -> Self
+    where
+        D: PartialEq + Send + Sync + 'static,
+        H: Fn(&str) -> D + Send + Sync + 'static,
+    {
+        self.matches_hash_with_msg(expected, hasher, "The code is not correct.")
+    }
+
+    /// Tests that the `hasher` maps the input to the `expected` digest, displaying a message upon failure.
+    pub fn matches_hash_with_msg<D, H, M>(self, expected: D, hasher: H, message: M) -> Self
+    where
+        D: PartialEq + Send + Sync + 'static,
+        H: Fn(&str) -> D + Send + Sync + 'static,
+        M: ToString + Send + Sync + 'static,
+    {
+        self.str_test_with_msg(move |s: &str| hasher(s) == expected, message)
+    }
+
+    /// Tests that the input can be used as a filename on common filesystems.
+    ///
+    /// The characters `/ \ : * ? " < > |` are rejected,
+    /// as well as names reserved on Windows, like `CON` or `PRN`.
+    pub fn filename_safe(self) -> Self {
+        self.filename_safe_with_reserved(true)
+    }
+
+    /// Tests that the input can be used as a filename on common filesystems,
+    /// rejecting names reserved on Windows only if `reject_reserved` is `true`.
+    ///
+    /// # Examples
+    ///
+    /// Check reserved names only when running on Windows.
+    /// ```no_run
+    /// let _filename = asking::text()
+    ///     .filename_safe_with_reserved(cfg!(windows))
+    ///     .ask_and_wait();
+    /// ```
+    pub fn filename_safe_with_reserved(self, reject_reserved: bool) -> Self {
+        const ILLEGAL: [char; 9] = ['/', '\\', ':', '*', '?', '"', '<', '>', '|'];
+        let question = self.str_test_with_msg(
+            |s: &str| !s.contains(&ILLEGAL[..]),
+            "The input can not contain any of the characters / \\ : * ? \" < > |.",
+        );
+        match reject_reserved {
+            true => question.str_test_with_feedback(|s: &str| {
+                let stem = s.split('.').next().unwrap_or_default().to_uppercase();
+                let reserved = matches!(stem.as_str(), "CON" | "PRN" | "AUX" | "NUL")
+                    || ((stem.starts_with("COM") || stem.starts_with("LPT"))
+                        && stem.len() == 4
+                        && stem[3..].chars().all(|c| ('1'..='9').contains(&c)));
+                match reserved {
+                    true => Err(Report::msg(format!("`{}` is a reserved name.", s))),
+                    false => Ok(()),
+                }
+            }),
+            false => question,
+        }
+    }
+
+    /// Set the parser for the input.
+    ///
+    /// # Remarks
+    ///
+    /// Errors will NOT be displayed if they occur.
+    /// Check out [`parser_with_feedback`] if you want to display a message upon failure.
+    ///
+    /// [`parser_with_feedback`]: #method.parser_with_feedback
+    pub fn parser<F>(mut self, parser: F) -> Self
+    where
+        F: Fn(&str) -> eyre::Result<T> + Send + Sync + 'static,
+    {
+        self.parser = (Arc::new(parser), false);
+        self
+    }
+}
+
+/// # Yes/No input
+impl<R, W> QuestionBuilder<bool, R, W> {
+    /// Set the parser to accept the answers of `locale`, ignoring case and accents.
+    ///
+    /// For example, with [`Locale::German`], "Ja" is `true`. Also, "true" and "false" are always accepted.
+    ///
+    /// # Remarks
+    ///
+    /// This replaces the parser, but keeps whether its errors are displayed.
+    ///
+    /// [`Locale::German`]: enum.Locale.html#variant.German
+    pub fn locale(mut self, locale: Locale) -> Self {
+        self.parser.0 =
+            Arc::new(move |s| crate::pattern::parse_yn_locale(s, locale).map_err(Report::new));
+        self
+    }
+
+    /// Ask a second yes/no question, displaying `message`, when the answer is `true`.
+    ///
+    /// If the second answer is `false`, the value is `false`.
+    /// This is useful to confirm destructive actions.
+    ///
+    /// # Remarks
+    ///
+    /// The second question is asked again until it gets a valid answer.
+    pub fn double_confirm(mut self, message: impl ToString) -> Self {
+        let cancel: Cancel<bool> = Arc::new(|value| match value {
+            true => Some(false),
+            false => None,
+        });
+        self.double_confirm = Some((message.to_string(), cancel));
+        self
+    }
+}
+
+/// # Text-line input
+impl<R, W> QuestionBuilder<String, R, W> {
+    /// Set which input is rejected for text lines.
+    ///
+    /// If `reject_control_chars` is `true`, input containing control characters, like tabs, is rejected.
+    /// If `reject_blank` is `true`, input that is empty or contains only whitespace is rejected.
+    ///
+    /// # Remarks
+    ///
+    /// This replaces the parser, but keeps whether its errors are displayed.
+    pub fn text_line_rules(mut self, reject_control_chars: bool, reject_blank: bool) -> Self {
+        self.parser.0 = Arc::new(move |s| {
+            crate::pattern::parse_text_line(s, reject_control_chars, reject_blank)
+                .map_err(Report::new)
+        });
+        self
+    }
+}
+
+/// # Text value
+///
+/// Strings are ordered lexicographically by their bytes, so, for example, `"Zebra" < "apple"`.
+/// Therefore, [`min`] and [`max`] compare uppercase letters before lowercase ones.
+///
+/// [`min`]: #method.min
+/// [`max`]: #method.max
+impl<R, W> QuestionBuilder<String, R, W> {
+    /// Asynchronously gets a text value from the user, writing it into `buffer`.
+    ///
+    /// The previous content of `buffer` is cleared, keeping its capacity.
+    /// Afterwards, the question starts over, as in [`ask_into`], so it can be asked again.
+    /// Reusing the question avoids allocating its settings for every value,
+    /// and the caller keeps a single `String` instead of receiving a new one each time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut question = asking::text().reader("first\nsecond\n".as_bytes());
+    /// let mut buffer = String::new();
+    /// async_std::task::block_on(async {
+    ///     question.ask_into_buffer(&mut buffer).await.unwrap();
+    ///     assert_eq!(buffer, "first");
+    ///     question.ask_into_buffer(&mut buffer).await.unwrap();
+    ///     assert_eq!(buffer, "second");
+    /// });
+    /// ```
+    ///
+    /// [`ask_into`]: #method.ask_into
+    pub async fn ask_into_buffer(&mut self, buffer: &mut String) -> Result<(), ProcessingError>
+    where
+        R: Read + Unpin,
+        W: Write + Unpin,
+    {
+        let restart = self.restart_point();
+        let result = self.ask_executor().await;
+        self.restart(&restart);
+        let (value, _) = result?;
+        buffer.clear();
+        buffer.push_str(&value);
+        Ok(())
+    }
+
+    /// Test if the value is between `low` and `high` in lexicographic order, including borders.
+    ///
+    /// # Remarks
+    ///
+    /// Bounds are compared with the whole value, so `"mango"` is not between `"a"` and `"m"`.
+    /// There is a default message you might want to change.
+    pub fn lex_range(self, low: impl ToString, high: impl ToString) -> Self {
+        let (low, high) = (low.to_string(), high.to_string());
+        let message = format!(
+            "The value must be alphabetically between {} and {}.",
+            low, high
+        );
+        self.lex_range_with_msg(low, high, message)
+    }
+
+    /// Test if the value is between `low` and `high` in lexicographic order, including borders,
+    /// displaying a message upon failure.
+    pub fn lex_range_with_msg<M>(self, low: impl ToString, high: impl ToString, message: M) -> Self
+    where
+        M: ToString + Send + Sync + 'static,
+    {
+        let (low, high) = (low.to_string(), high.to_string());
+        self.describe_bounds(&low, &high)
+            .min_max_with_msg(low, high, message)
+    }
+
+    /// Test if the value is between `low` and `high` in lexicographic order, including borders,
+    /// ignoring case.
+    ///
+    /// # Remarks
+    ///
+    /// There is a default message you might want to change.
+    pub fn lex_range_ignore_case(self, low: impl ToString, high: impl ToString) -> Self {
+        let (low, high) = (low.to_string(), high.to_string());
+        let message = format!(
+            "The value must be alphabetically between {} and {}.",
+            low, high
+        );
+        let question = self.describe_bounds(&low, &high);
+        let (low, high) = (low.to_lowercase(), high.to_lowercase());
+        question.test_with_msg(
+            move |value: &String| {
+                let value = value.to_lowercase();
+                (low <= value) && (value <= high)
+            },
+            message,
+        )
+    }
+}
+
+/// # Options
+impl<T, R, W> QuestionBuilder<T, R, W> {
+    /// Accept an unambiguous prefix of an option, ignoring case, as the option itself.
+    ///
+    /// For example, with options `commit` and `checkout`,
+    /// the input `com` is read as `commit`, while `c` is rejected as ambiguous.
+    ///
+    /// # Remarks
+    ///
+    /// Only options listed by patterns like [`select_listed`] or [`select_map`] are considered,
+    /// since [`select`] does not list its options.
+    /// Ambiguous input is rejected displaying the candidates.
+    ///
+    /// [`select_listed`]: fn.select_listed.html
+    /// [`select_map`]: fn.select_map.html
+    /// [`select`]: fn.select.html
+    pub fn allow_prefix(mut self) -> Self
+    where
+        T: 'static,
+    {
+        let options = self.config.options.clone();
+        let parser = self.parser.0.clone();
+        self.parser.0 = Arc::new(move |s| match crate::pattern::resolve_prefix(s, &options) {
+            Ok(Some(option)) => parser(option),
+            _ => parser(s),
+        });
+        let options = self.config.options.clone();
+        self.str_test_with_feedback(move |s| {
+            crate::pattern::resolve_prefix(s, &options)
+                .map(|_| ())
+                .map_err(Report::new)
+        })
+    }
+
+    /// Checks that every option listed by patterns like [`select_listed`] passes all tests,
+    /// without reading input.
+    ///
+    /// Useful to catch options that can never be accepted, for example,
+    /// because they are excluded by a [`not`] test.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InvalidOptions`] listing the options that can never be accepted.
+    ///
+    /// [`select_listed`]: fn.select_listed.html
+    /// [`not`]: #method.not
+    /// [`InvalidOptions`]: error/enum.ConfigError.html#variant.InvalidOptions
+    pub fn assert_options_valid(&self) -> Result<(), ConfigError> {
+        let options: Vec<String> = self
+            .config
+            .options
+            .iter()
+            .filter(|option| self.validate(option).is_err())
+            .cloned()
+            .collect();
+        match options.is_empty() {
+            true => Ok(()),
+            false => Err(ConfigError::InvalidOptions { options }),
+        }
+    }
+
+    #[cfg_attr(not(feature = "std"), allow(dead_code))]
+    pub(crate) fn options(mut self, options: Vec<String>) -> Self {
+        self.config.options = options;
+        self
+    }
+}
+
+/// # Key-value input
+impl<R, W> QuestionBuilder<HashMap<String, String>, R, W> {
+    /// Set the separators for key-value input.
+    ///
+    /// The `pair_separator` goes between a key and its value,
+    /// while the `entry_separator` goes between entries.
+    /// For example, `a:1,b:2` is read with `kv_separators(":", ",")`.
+    ///
+    /// # Remarks
+    ///
+    /// This replaces the parser, but keeps whether its errors are displayed.
+    pub fn kv_separators(
+        mut self,
+        pair_separator: impl ToString,
+        entry_separator: impl ToString,
+    ) -> Self {
+        let pair_separator = pair_separator.to_string();
+        let entry_separator = entry_separator.to_string();
+        self.parser.0 = Arc::new(move |s| {
+            crate::pattern::parse_key_values(s, &pair_separator, &entry_separator)
+                .map_err(Report::new)
+        });
+        self
+    }
+}
+
+/// # Questionnaire
+///
+/// Tests and parsers that can access previous answers of a [`Questionnaire`].
+///
+/// [`Questionnaire`]: struct.Questionnaire.html
+impl<T, R, W> QuestionBuilder<T, R, W> {
+    /// Add a new test for the value, with access to previous answers, displaying a message upon failure.
+    pub fn test_with_context<F, M>(self, test: F, message: M) -> Self
+    where
+        F: Fn(&T, &Context) -> bool + Send + Sync + 'static,
+        M: ToString + Send + Sync + 'static,
+    {
+        let context = self.config.context.clone();
+        self.test_with_msg(move |value: &T| test(value, &context), message)
+    }
+
+    /// Set the parser for the input, with access to previous answers.
+    ///
+    /// # Remarks
+    ///
+    /// Errors will NOT be displayed if they occur.
+    pub fn parser_with_context<F>(self, parser: F) -> Self
+    where
+        F: Fn(&str, &Context) -> eyre::Result<T> + Send + Sync + 'static,
+    {
+        let context = self.config.context.clone();
+        self.parser(move |s| parser(s, &context))
+    }
+
+    /// Require the input depending on previous answers.
+    ///
+    /// When asking, `required` is called with the answers so far and, if it returns `true`,
+    /// the input is [`required`], otherwise it is not.
+    ///
+    /// [`required`]: #method.required
+    pub fn required_if<F>(mut self, required: F) -> Self
+    where
+        F: Fn(&Context) -> bool + Send + Sync + 'static,
+    {
+        self.config.required_if = Some(Arc::new(required));
+        self
+    }
+
+    pub(crate) fn context_handle(&self) -> &Context {
+        &self.config.context
+    }
+}
+
+/// # Advanced methods
+impl<T, R, W> QuestionBuilder<T, R, W> {
+    /// Replaces the reader and writer, which are no longer known to be a terminal.
+    fn map_io<R2, W2, F>(self, io: F) -> QuestionBuilder<T, R2, W2>
+    where
+        F: FnOnce(BufReader<R>, BufWriter<W>) -> (BufReader<R2>, BufWriter<W2>),
+    {
+        let (reader, writer) = io(self.reader, self.writer);
+        QuestionBuilder {
+            reader,
+            writer,
+            config: QuestionConfig {
+                terminal: false,
+                ..self.config
+            },
+            attempt: self.attempt,
+            inputs_read: self.inputs_read,
+            session_used: self.session_used,
+            input_time: self.input_time,
+            asked_at: self.asked_at,
+            once: self.once,
+            default: self.default,
+            default_chain: self.default_chain,
+            feedback: self.feedback,
+            timed_feedback: self.timed_feedback,
+            parser: self.parser,
+            tests: self.tests,
+            double_confirm: self.double_confirm,
+            confirm_value: self.confirm_value,
+            reset_attempts_on: self.reset_attempts_on,
+            accept_if: self.accept_if,
+            round: self.round,
+            outcome_log: self.outcome_log,
+            async_tests: self.async_tests,
+        }
+    }
+
+    /// Change the reader.
+    pub fn reader<R2: Read>(self, other_reader: R2) -> QuestionBuilder<T, R2, W> {
+        let unbuffered = self.config.unbuffered_reader;
+        let mut question = self.map_io(|_, writer| {
+            let reader = match unbuffered {
+                true => BufReader::with_capacity(1, other_reader),
+                false => BufReader::new(other_reader),
+            };
+            (reader, writer)
+        });
+        question.config.reads_stdin = false;
+        question
+    }
+    /// Change the reader to one replaying `lines`, and then reaching the end of file.
+    ///
+    /// Useful for scripted runs, like demos and reproducible examples.
+    /// A newline is appended to lines that do not end with one.
+    pub fn scripted<I, S>(self, lines: I) -> QuestionBuilder<T, Cursor<Vec<u8>>, W>
+    where
+        I: IntoIterator<Item = S>,
+        S: ToString,
+    {
+        let mut script = String::new();
+        for line in lines {
+            script += &line.to_string();
+            if !script.ends_with('\n') {
+                script.push('\n');
+            }
+        }
+        self.reader(Cursor::new(script.into_bytes()))
+    }
+    /// Read from the reader one byte at a time, so no bytes past the line terminator are consumed.
+    ///
+    /// By default, the reader is buffered and may read ahead past the current line.
+    /// This breaks protocols where the following bytes belong to a different consumer,
+    /// like multiplexed streams. This mode trades performance for correctness in those cases.
+    ///
+    /// # Remarks
+    ///
+    /// It also applies to readers set afterwards by [`reader`].
+    /// Bytes already read ahead are discarded, so set it before asking.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let (answer, (rest, buffered)) = async_std::task::block_on(
+    ///     asking::text()
+    ///         .reader("hello\nnext".as_bytes())
+    ///         .unbuffered_reader()
+    ///         .ask_with_reader(),
+    /// );
+    /// assert_eq!(answer.unwrap(), "hello");
+    /// assert_eq!(rest, b"next");
+    /// assert!(buffered.is_empty());
+    /// ```
+    ///
+    /// [`reader`]: #method.reader
+    pub fn unbuffered_reader(mut self) -> Self
+    where
+        R: Read,
+    {
+        self.config.unbuffered_reader = true;
+        self.reader = BufReader::with_capacity(1, self.reader.into_inner());
+        self
+    }
+    /// Recover the reader, together with the bytes that were read but not consumed.
+    pub fn into_reader(self) -> (R, Vec<u8>) {
+        let buffered = self.reader.buffer().to_vec();
+        (self.reader.into_inner(), buffered)
+    }
+    /// Change the writer.
+    pub fn writer<W2: Write>(self, other_writer: W2) -> QuestionBuilder<T, R, W2> {
+        self.map_io(|reader, _| (reader, BufWriter::new(other_writer)))
+    }
+
+    /// Decompose the question into all its settings.
+    ///
+    /// Useful to inspect or replace specific settings, and then rebuild the question
+    /// with [`from_parts`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut parts = asking::question::<u8>().into_parts();
+    /// parts.config.error_formatter = std::sync::Arc::new(|s| format!("Error: {}\n", s));
+    /// let _question = asking::QuestionBuilder::from_parts(parts);
+    /// ```
+    ///
+    /// [`from_parts`]: #method.from_parts
+    pub fn into_parts(self) -> QuestionParts<T, R, W> {
+        QuestionParts {
+            reader: self.reader,
+            writer: self.writer,
+            config: self.config,
+            default_value: self.default,
+            default_chain: self.default_chain,
+            feedback: self.feedback,
+            feedback_with_timing: self.timed_feedback,
+            parser: self.parser.0,
+            parser_feedback: self.parser.1,
+            tests: self.tests,
+            double_confirm: self.double_confirm,
+            confirm_value: self.confirm_value,
+            reset_attempts_on: self.reset_attempts_on,
+            accept_if: self.accept_if,
+            round_to: self.round,
+            log_outcome: self.outcome_log,
+            async_tests: self.async_tests,
+        }
+    }
+
+    /// Build a question from all its settings, as given by [`into_parts`].
+    ///
+    /// [`into_parts`]: #method.into_parts
+    pub fn from_parts(parts: QuestionParts<T, R, W>) -> Self {
+        QuestionBuilder {
+            reader: parts.reader,
+            writer: parts.writer,
+            config: parts.config,
+            attempt: 0,
+            inputs_read: 0,
+            session_used: 0,
+            input_time: Duration::default(),
+            asked_at: Instant::now(),
+            once: false,
+            default: parts.default_value,
+            default_chain: parts.default_chain,
+            feedback: parts.feedback,
+            timed_feedback: parts.feedback_with_timing,
+            parser: (parts.parser, parts.parser_feedback),
+            tests: parts.tests,
+            double_confirm: parts.double_confirm,
+            confirm_value: parts.confirm_value,
+            reset_attempts_on: parts.reset_attempts_on,
+            accept_if: parts.accept_if,
+            round: parts.round_to,
+            outcome_log: parts.log_outcome,
+            async_tests: parts.async_tests,
+        }
+    }
+
+    /// Capture parsing errors as values, instead of asking again.
+    ///
+    /// The value is `Ok` if the input was parsed, and otherwise `Err` with the input,
+    /// after applying the [`preparser`]. This is useful for "best effort" forms.
+    ///
+    /// # Remarks
+    ///
+    /// Tests on the input, like [`str_test`], still ask again upon failure.
+    /// Tests on the value, [`feedback`] and [`confirm_value`] only apply to parsed values,
+    /// while the [`default_value`] is returned as parsed.
+    ///
+    /// [`preparser`]: #method.preparser
+    /// [`str_test`]: #method.str_test
+    /// [`feedback`]: #method.feedback
+    /// [`confirm_value`]: #method.confirm_value
+    /// [`default_value`]: #method.default_value
+    pub fn capture_parse(self) -> QuestionBuilder<Result<T, String>, R, W>
+    where
+        T: 'static,
+    {
+        let parser = self.parser.0;
+        let feedback = self.feedback;
+        let tests = self.tests.into_iter().map(|(test, display)| {
+            let test: Test<Result<T, String>> = Arc::new(move |value| match value {
+                Ok(value) => test(value),
+                Err(_) => Ok(()),
+            });
+            (test, display)
+        });
+        QuestionBuilder {
+            reader: self.reader,
+            writer: self.writer,
+            config: self.config,
+            attempt: self.attempt,
+            inputs_read: self.inputs_read,
+            session_used: self.session_used,
+            input_time: self.input_time,
+            asked_at: self.asked_at,
+            once: self.once,
+            default: self.default.map(Ok),
+            default_chain: self.default_chain.map(|chain| {
+                let parser = parser.clone();
+                let chain: DefaultChain<Result<T, String>> = Arc::new(move |_| {
+                    let resolved = chain(parser.clone());
+                    Box::pin(async move { resolved.await.map(Ok) })
+                });
+                chain
+            }),
+            feedback: Arc::new(move |value| match value {
+                Ok(value) => feedback(value),
+                Err(_) => String::default(),
+            }),
+            timed_feedback: self.timed_feedback.map(|feedback| {
+                let feedback: TimedFeedback<Result<T, String>> =
+                    Arc::new(move |value, elapsed| match value {
+                        Ok(value) => feedback(value, elapsed),
+                        Err(_) => String::default(),
+                    });
+                feedback
+            }),
+            parser: (
+                Arc::new(move |s| Ok(parser(s).map_err(|_| s.to_string()))),
+                self.parser.1,
+            ),
+            tests: tests.collect(),
+            double_confirm: self.double_confirm.map(|(message, cancel)| {
+                let cancel: Cancel<Result<T, String>> = Arc::new(move |value| match value {
+                    Ok(value) => cancel(value).map(Ok),
+                    Err(_) => None,
+                });
+                (message, cancel)
+            }),
+            confirm_value: self.confirm_value.map(|template| {
+                let template: Template<Result<T, String>> = Arc::new(move |value| match value {
+                    Ok(value) => template(value),
+                    Err(_) => None,
+                });
+                template
+            }),
+            reset_attempts_on: self.reset_attempts_on.map(|predicate| {
+                let predicate: Predicate<Result<T, String>> = Arc::new(move |value| match value {
+                    Ok(value) => predicate(value),
+                    Err(_) => false,
+                });
+                predicate
+            }),
+            accept_if: self.accept_if.map(|predicate| {
+                let predicate: Predicate<Result<T, String>> = Arc::new(move |value| match value {
+                    Ok(value) => predicate(value),
+                    Err(_) => false,
+                });
+                predicate
+            }),
+            round: self.round.map(|round| {
+                let round: Round<Result<T, String>> = Arc::new(move |value| value.map(&*round));
+                round
+            }),
+            outcome_log: self.outcome_log.map(|(writer, format)| {
+                let format: Feedback<Result<T, String>> = Arc::new(move |value| match value {
+                    Ok(value) => format(value),
+                    Err(input) => format!("{:?}", input),
+                });
+                (writer, format)
+            }),
+            async_tests: self
+                .async_tests
+                .into_iter()
+                .map(|test| {
+                    let test: AsyncTest<Result<T, String>> =
+                        Arc::new(move |value, progress| match value {
+                            Ok(value) => test(value, progress),
+                            Err(_) => Box::pin(async { Ok(()) }),
+                        });
+                    test
+                })
+                .collect(),
+        }
+    }
+
+    /// Runs the preparsers, tests and parser on `input`, as if it was read,
+    /// without reading or writing anything.
+    ///
+    /// Useful to unit-test the validation of a question.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error, as it would be reported while asking.
+    ///
+    /// # Remarks
+    ///
+    /// The [`transform_input_async`] transformation and the [`default_value`] are not applied.
+    ///
+    /// [`transform_input_async`]: #method.transform_input_async
+    /// [`default_value`]: #method.default_value
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let question = asking::question::<u8>().max(10);
+    /// assert_eq!(question.validate("7").unwrap(), 7);
+    /// assert!(question.validate("42").is_err());
+    /// ```
+    pub fn validate(&self, input: &str) -> eyre::Result<T> {
+        let input = self.preparse(input.to_string());
+        for str_test in &self.config.str_tests {
+            (str_test.0)(&input).wrap_err("while testing the input")?;
+        }
+        let value = (self.parser.0)(&input).wrap_err("while parsing the input")?;
+        if self.is_accepted(&value) {
+            return Ok(self.rounded(value));
+        }
+        for test in &self.tests {
+            (test.0)(&value).wrap_err("while validating the value")?;
+        }
+        Ok(self.rounded(value))
+    }
+
+    /// Returns a machine-readable description of the question,
+    /// to render it outside of a terminal and check the answer with [`validate`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let descriptor = asking::number_in_range(1, 10).default_value(5).describe();
+    /// assert_eq!(descriptor.help, "Enter a number between 1 and 10.\n");
+    /// assert_eq!(descriptor.default.as_deref(), Some("5"));
+    /// assert_eq!(descriptor.min.as_deref(), Some("1"));
+    /// assert_eq!(descriptor.max.as_deref(), Some("10"));
+    /// ```
+    ///
+    /// [`validate`]: #method.validate
+    pub fn describe(&self) -> PromptDescriptor
+    where
+        T: ToString,
+    {
+        PromptDescriptor {
+            message: self.affixed_message(self.attempt + 1),
+            help: self.config.help.clone(),
+            required: self.config.required,
+            default: self.default.as_ref().map(ToString::to_string),
+            options: self.config.options.clone(),
+            min_length: self.config.length_bounds.0,
+            max_length: self.config.length_bounds.1,
+            min: self.config.value_bounds.0.clone(),
+            max: self.config.value_bounds.1.clone(),
+        }
+    }
+
+    /// Records the bounds of the value for the [`describe`] method.
+    ///
+    /// [`describe`]: #method.describe
+    #[cfg_attr(not(feature = "std"), allow(dead_code))]
+    pub(crate) fn describe_bounds(mut self, lower: &impl ToString, upper: &impl ToString) -> Self {
+        self.config.value_bounds = (Some(lower.to_string()), Some(upper.to_string()));
+        self
+    }
+
+    fn rounded(&self, value: T) -> T {
+        match &self.round {
+            Some(round) => round(value),
+            None => value,
+        }
+    }
+
+    fn is_accepted(&self, proposal: &T) -> bool {
+        match &self.accept_if {
+            Some(accept_if) => accept_if(proposal),
+            None => false,
+        }
+    }
+
+    /// Checks that the configuration is not contradictory, without reading input.
+    ///
+    /// Calling this method is optional, but it helps catching setup bugs before asking.
+    /// Whether the reader is already at the end of file is not checked here,
+    /// since it requires reading, see [`validate_config_with_reader`].
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ConfigError`] describing the first contradiction found.
+    ///
+    /// [`ConfigError`]: error/enum.ConfigError.html
+    /// [`validate_config_with_reader`]: #method.validate_config_with_reader
+    pub fn validate_config(&self) -> Result<(), ConfigError> {
+        if let Some((0, _)) = self.config.attempts {
+            return Err(ConfigError::NoAttempts);
+        }
+        match self.config.executor {
+            Executor::Timeout(duration)
+            | Executor::TimeoutInputOnly(duration)
+            | Executor::IdleTimeout(duration)
+                if duration.is_zero() =>
+            {
+                return Err(ConfigError::NoTime)
+            }
+            _ => (),
+        }
+        if self.config.required && self.default.is_some() && !self.config.attempts_then_default {
+            return Err(ConfigError::RequiredWithDefault);
+        }
+        if self.config.mask
+            && self.config.require_tty_for_mask
+            && !self.can_mask()
+            && self.config.secret_from_path.is_none()
+        {
+            return Err(ConfigError::MaskUnavailable);
+        }
+        if self.reads_utf8_only() && self.config.input_encoding != Encoding::Utf8 {
+            return Err(ConfigError::EncodingUnsupported);
+        }
+        Ok(())
+    }
+
+    /// Checks the configuration, as [`validate_config`] does, and also that
+    /// the reader is not already at the end of file when input is [`required`].
+    ///
+    /// The reader is only checked outside a terminal, where waiting for the user makes no sense.
+    /// Input is peeked, not consumed, so asking afterwards still reads all of it.
+    /// Errors while reading are left for asking to report.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ConfigError`] describing the first contradiction found.
+    ///
+    /// [`validate_config`]: #method.validate_config
+    /// [`required`]: #method.required
+    /// [`ConfigError`]: error/enum.ConfigError.html
+    pub async fn validate_config_with_reader(&mut self) -> Result<(), ConfigError>
+    where
+        R: Read + Unpin,
+    {
+        self.validate_config()?;
+        if self.config.required && !self.config.terminal && self.default.is_none() {
+            let reader = &mut self.reader;
+            let at_eof = std::future::poll_fn(|cx| {
+                Pin::new(&mut *reader)
+                    .poll_fill_buf(cx)
+                    .map(|buffer| buffer.map(|buffer| buffer.is_empty()))
+            })
+            .await;
+            if let Ok(true) = at_eof {
+                return Err(ConfigError::RequiredAtEof);
+            }
+        }
+        Ok(())
+    }
+
+    /// Change the way errors are displayed.
+    ///
+    /// # Remarks
+    ///
+    /// This can be useful if to:
+    /// - Change the default behaviour of appending a newline.
+    /// - Not display a message upon an error.
+    ///
+    /// # Examples
+    ///
+    /// Silent errors. Now, only messages will be displayed.
+    /// ```no_run
+    /// # #[async_std::main]
+    /// # async fn main() -> eyre::Result<()> {
+    ///     let _num = asking::question()
+    ///         .message("Give me a number bigger than 3, please. I will not tell you anything else.\n")
+    ///         .test(|i: &u32| *i > 3)
+    ///         .error_formatter(|_| "".to_string())
+    ///         .ask()
+    ///         .await?;
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn error_formatter<F>(mut self, error_formatter: F) -> Self
+    where
+        F: Fn(String) -> String + Send + Sync + 'static,
+    {
+        self.config.error_formatter = Arc::new(error_formatter);
+        self
+    }
+
+    /// Display errors as they are, without appending a newline.
+    ///
+    /// Convenience method for `error_formatter(|s| s)`.
+    pub fn error_no_newline(self) -> Self {
+        self.error_formatter(|s| s)
+    }
+
+    /// Toggle the feedback from the parser.
+    ///
+    /// If activated, errors from parsing will be displayed.
+    pub fn parser_feedback_toggle(mut self) -> Self {
+        self.parser.1 = !self.parser.1;
+        self
+    }
+
+    /// Set the parser for the input.
+    ///
+    /// Errors will be displayed if they occur.
+    pub fn parser_with_feedback<F>(mut self, parser: F) -> Self
+    where
+        F: Fn(&str) -> eyre::Result<T> + Send + Sync + 'static,
+    {
+        self.parser = (Arc::new(parser), true);
+        self
+    }
+
+    /// Add a test over the unparsed input.
+    ///
+    /// Errors will be displayed if they occur.
+    pub fn str_test_with_feedback<F>(mut self, str_test: F) -> Self
+    where
+        F: Fn(&str) -> eyre::Result<()> + Send + Sync + 'static,
+    {
+        self.config.str_tests.push((Arc::new(str_test), true));
+        self
+    }
+
+    /// Add a test over the parsed input
+    ///
+    /// Errors will be displayed if they occur.
+    pub fn test_with_feedback<F>(mut self, test: F) -> Self
+    where
+        F: Fn(&T) -> eyre::Result<()> + Send + Sync + 'static,
+    {
+        self.tests.push((Arc::new(test), true));
+        self
+    }
+}
+
+impl<T, R, W> Debug for QuestionBuilder<T, R, W>
+where
+    T: Debug,
+    R: Read + Debug,
+    W: Write + Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("QuestionBuilder")
+            .field("reader", &self.reader)
+            .field("writer", &self.writer)
+            .field("help", &self.config.help)
+            .field("default", &self.default)
+            .field("executor", &self.config.executor)
+            .field("required", &self.config.required)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use async_std::io;
+
+    type Output = async_dup::Arc<async_dup::Mutex<Vec<u8>>>;
+
+    fn output() -> Output {
+        async_dup::Arc::new(async_dup::Mutex::new(Vec::new()))
+    }
+
+    fn written(output: &Output) -> String {
+        String::from_utf8(output.lock().clone()).unwrap()
+    }
+
+    /// Reader or writer that waits `delay` before each operation,
+    /// reading one byte at a time.
+    struct Delayed<I> {
+        inner: I,
+        delay: Duration,
+        sleep: Option<std::pin::Pin<Box<dyn std::future::Future<Output = ()> + Send>>>,
+    }
+
+    impl<I> Delayed<I> {
+        fn new(inner: I, delay: Duration) -> Self {
+            Delayed {
+                inner,
+                delay,
+                sleep: None,
+            }
+        }
+
+        fn poll_delay(&mut self, cx: &mut std::task::Context<'_>) -> std::task::Poll<()> {
+            let delay = self.delay;
+            let sleep = self
+                .sleep
+                .get_or_insert_with(|| Box::pin(async_std::task::sleep(delay)));
+            let poll = std::future::Future::poll(sleep.as_mut(), cx);
+            if poll.is_ready() {
+                self.sleep = None;
+            }
+            poll
+        }
+    }
+
+    impl<I: Read + Unpin> Read for Delayed<I> {
+        fn poll_read(
+            mut self: std::pin::Pin<&mut Self>,
+            cx: &mut std::task::Context<'_>,
+            buf: &mut [u8],
+        ) -> std::task::Poll<std::io::Result<usize>> {
+            if self.poll_delay(cx).is_pending() {
+                return std::task::Poll::Pending;
+            }
+            let length = buf.len().min(1);
+            std::pin::Pin::new(&mut self.inner).poll_read(cx, &mut buf[..length])
+        }
+    }
+
+    impl<I: Write + Unpin> Write for Delayed<I> {
+        fn poll_write(
+            mut self: std::pin::Pin<&mut Self>,
+            cx: &mut std::task::Context<'_>,
+            buf: &[u8],
+        ) -> std::task::Poll<std::io::Result<usize>> {
+            if self.poll_delay(cx).is_pending() {
+                return std::task::Poll::Pending;
+            }
+            std::pin::Pin::new(&mut self.inner).poll_write(cx, buf)
+        }
+
+        fn poll_flush(
+            mut self: std::pin::Pin<&mut Self>,
+            cx: &mut std::task::Context<'_>,
+        ) -> std::task::Poll<std::io::Result<()>> {
+            std::pin::Pin::new(&mut self.inner).poll_flush(cx)
+        }
+
+        fn poll_close(
+            mut self: std::pin::Pin<&mut Self>,
+            cx: &mut std::task::Context<'_>,
+        ) -> std::task::Poll<std::io::Result<()>> {
+            std::pin::Pin::new(&mut self.inner).poll_close(cx)
+        }
+    }
+
+    #[test]
+    fn is_send() {
+        if false {
+            let question = QuestionBuilder::new_fromstr(io::stdin(), io::stdout()).ask();
+            fn is_send<T: Send>(_: &T) {}
+            is_send(&question);
+            let _answer: bool = async_std::task::block_on(question).unwrap();
+        }
+    }
+
+    #[test]
+    fn kv_separators() {
+        let out = output();
+        let answer = crate::key_values()
+            .reader("a=1\na:1, b:2\n".as_bytes())
+            .writer(out.clone())
+            .kv_separators(":", ",")
+            .parser_feedback_toggle()
+            .ask_and_wait()
+            .unwrap();
+        assert_eq!(answer.len(), 2);
+        assert_eq!(answer["a"], "1");
+        assert_eq!(answer["b"], "2");
+        assert_eq!(
+            written(&out),
+            "Entry `a=1` is not of the form `key:value`.\n"
+        );
+    }
+
+    #[test]
+    fn disconnected() {
+        struct BrokenReader;
+        impl Read for BrokenReader {
+            fn poll_read(
+                self: std::pin::Pin<&mut Self>,
+                _: &mut std::task::Context<'_>,
+                _: &mut [u8],
+            ) -> std::task::Poll<std::io::Result<usize>> {
+                std::task::Poll::Ready(Err(std::io::ErrorKind::BrokenPipe.into()))
+            }
+        }
+
+        let result = crate::text()
+            .reader(BrokenReader)
+            .writer(output())
+            .ask_and_wait();
+        assert!(matches!(result, Err(ProcessingError::Disconnected { .. })));
+    }
+
+    #[test]
+    fn timeout_input_only() {
+        let question = || {
+            crate::yn()
+                .reader("yes\n".as_bytes())
+                .writer(Delayed::new(output(), Duration::from_millis(200)))
+                .feedback(|_| "Great!\n".to_string())
+        };
+
+        let result = question()
+            .timeout(Duration::from_millis(100))
+            .ask_and_wait();
+        assert!(matches!(result, Err(ProcessingError::Timeout { .. })));
+
+        let result = question()
+            .timeout_input_only(Duration::from_millis(100))
+            .ask_and_wait();
+        assert!(result.unwrap());
+    }
+
+    #[test]
+    fn ask_with_raw() {
+        let question = QuestionBuilder::new_fromstr("007\n".as_bytes(), output());
+        let (value, raw): (u8, String) =
+            async_std::task::block_on(question.ask_with_raw()).unwrap();
+        assert_eq!(value, 7);
+        assert_eq!(raw, "007");
+    }
+
+    #[test]
+    fn text_line_rules() {
+        let out = output();
+        let answer = crate::text_line()
+            .reader("a\tb\n\t\n".as_bytes())
+            .writer(out.clone())
+            .preparser(|s| s.trim_end_matches('\n').to_string())
+            .text_line_rules(false, true)
+            .parser_feedback_toggle()
+            .ask_and_wait()
+            .unwrap();
+        assert_eq!(answer, "a\tb");
+
+        let out = output();
+        let answer = crate::text_line()
+            .reader("a\tb\n\t\nab\n".as_bytes())
+            .writer(out.clone())
+            .preparser(|s| s.trim_end_matches('\n').to_string())
+            .parser_feedback_toggle()
+            .ask_and_wait()
+            .unwrap();
+        assert_eq!(answer, "ab");
+        assert_eq!(
+            written(&out),
+            "The input can not contain control characters.\n".repeat(2)
+        );
+    }
+
+    #[test]
+    fn attempts_then_default() {
+        let answer = QuestionBuilder::new_fromstr("a\nb\n".as_bytes(), output())
+            .attempts(2)
+            .default_value(3)
+            .required()
+            .attempts_then_default()
+            .ask_and_wait()
+            .unwrap();
+        assert_eq!(answer, 3);
+
+        let result = QuestionBuilder::<u8, _, _>::new_fromstr("a\nb\n".as_bytes(), output())
+            .attempts(2)
+            .attempts_then_default()
+            .ask_and_wait();
+        assert!(matches!(result, Err(ProcessingError::NoMoreAttempts)));
+    }
+
+    #[test]
+    fn str_test_mode() {
+        let out = output();
+        let answer = crate::text()
+            .reader("a b\nabc\n".as_bytes())
+            .writer(out.clone())
+            .min_length_with_msg(4, "Too short.")
+            .str_test_with_msg(|s| !s.contains(' '), "Contains spaces.")
+            .str_test_mode(TestMode::AllFailures)
+            .attempts(2)
+            .ask_and_wait();
+        assert!(matches!(answer, Err(ProcessingError::NoMoreAttempts)));
+        assert_eq!(written(&out), "Too short.\nContains spaces.\nToo short.\n");
+    }
+
+    #[test]
+    fn require_tty_for_mask() {
+        let result = crate::password()
+            .reader("secret\n".as_bytes())
+            .writer(output())
+            .ask_and_wait();
+        assert!(matches!(result, Err(ProcessingError::MaskUnavailable)));
+
+        let answer = crate::password()
+            .reader("secret\n".as_bytes())
+            .writer(output())
+            .require_tty_for_mask_toggle()
+            .ask_and_wait()
+            .unwrap();
+        assert_eq!(answer, "secret");
+    }
+
+    #[test]
+    fn is_empty_when() {
+        let answer = crate::text()
+            .reader("  \t\n".as_bytes())
+            .writer(output())
+            .preparser(|s| s.trim_end_matches('\n').to_string())
+            .is_empty_when(|s| s.trim().is_empty())
+            .default_value("default".to_string())
+            .ask_and_wait()
+            .unwrap();
+        assert_eq!(answer, "default");
+    }
+
+    #[test]
+    fn feedback_chunk_size() {
+        let out = output();
+        let report = "report\n".repeat(10_000);
+        let feedback = report.clone();
+        crate::text()
+            .reader("ok\n".as_bytes())
+            .writer(out.clone())
+            .feedback(move |_| feedback.clone())
+            .feedback_chunk_size(1000)
+            .ask_and_wait()
+            .unwrap();
+        assert_eq!(written(&out), report);
+    }
+
+    #[test]
+    fn message_fn() {
+        let out = output();
+        crate::text()
+            .reader("\nok\n".as_bytes())
+            .writer(out.clone())
+            .message_fn(|attempt| format!("Attempt {}: ", attempt))
+            .min_length_with_msg(1, "Empty.")
+            .ask_and_wait()
+            .unwrap();
+        assert_eq!(written(&out), "Attempt 1: Empty.\nAttempt 2: ");
+    }
+
+    #[test]
+    fn indent() {
+        let out = output();
+        crate::text()
+            .reader("ok\n".as_bytes())
+            .writer(out.clone())
+            .message("First line\nSecond line\n")
+            .feedback(|s| format!("Got {}\n", s))
+            .indent("> ")
+            .ask_and_wait()
+            .unwrap();
+        assert_eq!(written(&out), "> First line\n> Second line\n> Got ok\n");
+    }
+
+    #[test]
+    fn channel_reader() {
+        let (sender, receiver) = async_std::channel::unbounded();
+        let mut reader = crate::ChannelReader::new(receiver);
+        async_std::task::block_on(async {
+            sender.send("yes".to_string()).await.unwrap();
+            sender.send("no\n".to_string()).await.unwrap();
+            sender.close();
+        });
+
+        let first = crate::yn()
+            .reader(&mut reader)
+            .writer(output())
+            .ask_and_wait()
+            .unwrap();
+        let second = crate::yn()
+            .reader(&mut reader)
+            .writer(output())
+            .ask_and_wait()
+            .unwrap();
+        assert!(first);
+        assert!(!second);
+    }
+
+    #[test]
+    fn channel_writer() {
+        let (sender, receiver) = async_std::channel::unbounded();
+        crate::text()
+            .reader("Ana\n".as_bytes())
+            .writer(crate::ChannelWriter::new(sender))
+            .message("Name? ")
+            .feedback(|name| format!("Hi {}!\n", name))
+            .ask_and_wait()
+            .unwrap();
+        let messages: Vec<String> = std::iter::from_fn(|| receiver.try_recv().ok()).collect();
+        assert_eq!(messages, vec!["Name? ", "Hi Ana!\n"]);
+    }
+
+    #[test]
+    fn idle_timeout() {
+        let steady = Delayed::new("a\nb\nc\nok\n".as_bytes(), Duration::from_millis(30));
+        let answer = crate::text()
+            .reader(steady)
+            .writer(output())
+            .min_length(2)
+            .idle_timeout(Duration::from_millis(100))
+            .ask_and_wait()
+            .unwrap();
+        assert_eq!(answer, "ok");
+
+        let paused = Delayed::new("ok\n".as_bytes(), Duration::from_millis(200));
+        let result = crate::text()
+            .reader(paused)
+            .writer(output())
+            .idle_timeout(Duration::from_millis(100))
+            .ask_and_wait();
+        assert!(matches!(
+            result,
+            Err(ProcessingError::Timeout { duration, .. }) if duration == Duration::from_millis(100)
+        ));
+    }
+
+    #[test]
+    fn error_no_newline() {
+        let out = output();
+        crate::text()
+            .reader("a\nab\n".as_bytes())
+            .writer(out.clone())
+            .min_length_with_msg(2, "Too short.")
+            .error_no_newline()
+            .ask_and_wait()
+            .unwrap();
+        assert_eq!(written(&out), "Too short.");
+    }
+
+    #[test]
+    fn allow_prefix() {
+        let out = output();
+        let answer = crate::select_listed(vec!["commit".to_string(), "checkout".to_string()])
+            .reader("c\ncom\n".as_bytes())
+            .writer(out.clone())
+            .allow_prefix()
+            .ask_and_wait()
+            .unwrap();
+        assert_eq!(answer, "commit");
+        assert_eq!(
+            written(&out),
+            "Input `c` is ambiguous, it could be: commit, checkout.\n"
+        );
+    }
+
+    #[test]
+    fn render_prompt() {
+        let out = output();
+        let question = crate::text()
+            .reader("ok\n".as_bytes())
+            .writer(out.clone())
+            .message("Name?\n")
+            .attempts_with_feedback(3, |n| format!("({} attempts left) ", n));
+        assert_eq!(question.render_prompt(), "Name?\n(3 attempts left) ");
+        assert_eq!(question.render_prompt(), "Name?\n(3 attempts left) ");
+
+        question.ask_and_wait().unwrap();
+        assert_eq!(written(&out), "Name?\n(3 attempts left) ");
+    }
+
+    #[test]
+    fn questionnaire() {
+        let out = output();
+        let mut questionnaire = crate::Questionnaire::new();
+        async_std::task::block_on(async {
+            let start: u32 = questionnaire
+                .ask(
+                    "start",
+                    QuestionBuilder::new_fromstr("5\n".as_bytes(), output()),
+                )
+                .await
+                .unwrap();
+            let end: u32 = questionnaire
+                .ask(
+                    "end",
+                    QuestionBuilder::new_fromstr("3\n7\n".as_bytes(), out.clone())
+                        .test_with_context(
+                            |end: &u32, context| Some(*end) > context.get("start"),
+                            "The end must come after the start.",
+                        ),
+                )
+                .await
+                .unwrap();
+            assert_eq!((start, end), (5, 7));
+        });
+        assert_eq!(written(&out), "The end must come after the start.\n");
+        assert_eq!(questionnaire.context().get::<u32>("end"), Some(7));
+    }
+
+    #[test]
+    fn feedback_matching() {
+        let question = |input: &'static str, out: Output| {
+            let is_two: fn(&u32) -> bool = |value| *value == 2;
+            let is_four: fn(&u32) -> bool = |value| *value == 4;
+            QuestionBuilder::new_fromstr(input.as_bytes(), out)
+                .feedback_matching([(is_two, "You chose 2!"), (is_four, "You chose 4!")])
+                .ask_and_wait()
+                .unwrap()
+        };
+        let out = output();
+        question("2\n", out.clone());
+        assert_eq!(written(&out), "You chose 2!");
+        let out = output();
+        question("4\n", out.clone());
+        assert_eq!(written(&out), "You chose 4!");
+    }
+
+    #[test]
+    fn confirm_retries() {
+        let out = output();
+        let answer = crate::password()
+            .reader("secret\nsecert\nsecret\n".as_bytes())
+            .writer(out.clone())
+            .require_tty_for_mask_toggle()
+            .message("Password: ")
+            .confirm("Again: ")
+            .confirm_retries(1)
+            .ask_and_wait()
+            .unwrap();
+        assert_eq!(answer, "secret");
+        assert_eq!(
+            written(&out),
+            "Password: Again: The entries do not match.\nAgain: "
+        );
+
+        let result = crate::text()
+            .reader("secret\nsecert\n".as_bytes())
+            .writer(output())
+            .confirm("Again: ")
+            .confirm_retries(0)
+            .ask_and_wait();
+        assert!(matches!(result, Err(ProcessingError::ConfirmationMismatch)));
+    }
+
+    #[test]
+    fn step() {
+        let out = output();
+        let answer: u32 = QuestionBuilder::new_fromstr("7\n10\n".as_bytes(), out.clone())
+            .step(5)
+            .ask_and_wait()
+            .unwrap();
+        assert_eq!(answer, 10);
+        assert_eq!(written(&out), "The value must be a multiple of 5.\n");
+
+        let answer: u32 = QuestionBuilder::new_fromstr("7\n".as_bytes(), output())
+            .step(0)
+            .ask_and_wait()
+            .unwrap();
+        assert_eq!(answer, 7);
+
+        let answer: i32 = QuestionBuilder::new_fromstr("-2147483648\n".as_bytes(), output())
+            .step(-1)
+            .ask_and_wait()
+            .unwrap();
+        assert_eq!(answer, i32::MIN);
+    }
+
+    #[test]
+    fn into_report() {
+        let report: eyre::Report = crate::text()
+            .reader("".as_bytes())
+            .writer(output())
+            .ask_and_wait()
+            .unwrap_err()
+            .into();
+        assert_eq!(report.to_string(), "EOF reached while asking for input.");
+    }
+
+    #[test]
+    fn double_confirm() {
+        let question = |input: &'static str| {
+            crate::yn()
+                .reader(input.as_bytes())
+                .writer(output())
+                .double_confirm("Are you SURE? ")
+                .ask_and_wait()
+                .unwrap()
+        };
+        assert!(!question("yes\nno\n"));
+        assert!(question("yes\nyes\n"));
+        assert!(!question("no\n"));
+    }
+
+    #[test]
+    fn line_terminator() {
+        let answer = crate::text()
+            .reader("bad\rgood\r".as_bytes())
+            .writer(output())
+            .line_terminator(b'\r')
+            .not("bad".to_string())
+            .ask_and_wait()
+            .unwrap();
+        assert_eq!(answer, "good");
+    }
+
+    #[test]
+    fn retry_policy() {
+        let question = |policy| {
+            QuestionBuilder::<u8, _, _>::new_fromstr("a\nb\n3\n".as_bytes(), output())
+                .retry_policy(policy)
+                .ask_and_wait()
+        };
+
+        assert!(matches!(
+            question(RetryPolicy::Fixed(2)),
+            Err(ProcessingError::NoMoreAttempts)
+        ));
+        assert_eq!(question(RetryPolicy::Unlimited).unwrap(), 3);
+
+        let start = Instant::now();
+        let policy = RetryPolicy::WithDelay {
+            attempts: 3,
+            delay: Duration::from_millis(50),
+        };
+        assert_eq!(question(policy).unwrap(), 3);
+        assert!(start.elapsed() >= Duration::from_millis(100));
+    }
+
+    #[test]
+    fn add_preparser() {
+        let answer = crate::text()
+            .reader("  Hello World  \n".as_bytes())
+            .writer(output())
+            .add_preparser(|s| s.trim_start().to_string())
+            .add_preparser(|s| s.to_lowercase())
+            .add_preparser(|s| s.replace(' ', "_"))
+            .ask_and_wait()
+            .unwrap();
+        assert_eq!(answer, "hello_world");
+
+        let answer = crate::text()
+            .reader(" a \n".as_bytes())
+            .writer(output())
+            .clear_preparsers()
+            .add_preparser(|s| s.replace(' ', "_"))
+            .ask_and_wait()
+            .unwrap();
+        assert_eq!(answer, "_a_\n");
+    }
+
+    #[test]
+    fn on_timing() {
+        let timing = Arc::new(std::sync::Mutex::new(None));
+        let recorded = timing.clone();
+        crate::text()
+            .reader(Delayed::new("ok\n".as_bytes(), Duration::from_millis(20)))
+            .writer(output())
+            .on_timing(move |elapsed| *recorded.lock().unwrap() = Some(elapsed))
+            .ask_and_wait()
+            .unwrap();
+        let elapsed = timing.lock().unwrap().unwrap();
+        assert!(elapsed >= Duration::from_millis(60));
+        assert!(elapsed < Duration::from_secs(5));
+
+        let timing = Arc::new(std::sync::Mutex::new(None));
+        let recorded = timing.clone();
+        let _ = crate::text()
+            .reader("".as_bytes())
+            .writer(output())
+            .on_timing(move |elapsed| *recorded.lock().unwrap() = Some(elapsed))
+            .ask_and_wait();
+        assert!(timing.lock().unwrap().is_none());
+    }
+
+    #[test]
+    fn scripted() {
+        let answer = crate::text()
+            .scripted(vec!["bad", "good"])
+            .writer(output())
+            .not("bad".to_string())
+            .ask_and_wait()
+            .unwrap();
+        assert_eq!(answer, "good");
+    }
+
+    #[test]
+    fn filename_safe() {
+        let out = output();
+        let answer = crate::text()
+            .reader("a/b\nCON\ncon.txt\nreport_2025\n".as_bytes())
+            .writer(out.clone())
+            .filename_safe()
+            .ask_and_wait()
+            .unwrap();
+        assert_eq!(answer, "report_2025");
+        assert_eq!(
+            written(&out),
+            "The input can not contain any of the characters / \\ : * ? \" < > |.\n\
+            `CON` is a reserved name.\n\
+            `con.txt` is a reserved name.\n"
+        );
+
+        let answer = crate::text()
+            .reader("CON\n".as_bytes())
+            .writer(output())
+            .filename_safe_with_reserved(false)
+            .ask_and_wait()
+            .unwrap();
+        assert_eq!(answer, "CON");
+    }
+
+    #[test]
+    fn confirm_value() {
+        let out = output();
+        let answer: u8 = QuestionBuilder::new_fromstr("12\nno\n21\nyes\n".as_bytes(), out.clone())
+            .confirm_value(|age| format!("You entered {}, is this correct? (y/n) ", age))
+            .ask_and_wait()
+            .unwrap();
+        assert_eq!(answer, 21);
+        assert_eq!(
+            written(&out),
+            "You entered 12, is this correct? (y/n) You entered 21, is this correct? (y/n) "
+        );
+    }
+
+    #[test]
+    fn report_context() {
+        let out = output();
+        let mut question = QuestionBuilder::<u8, _, _>::new_fromstr("".as_bytes(), out.clone())
+            .parser_feedback_toggle()
+            .test_with_msg(|value| *value > 3, "Too small.");
+        async_std::task::block_on(async {
+            let report = question.parse_input("a").await.unwrap_err();
+            assert_eq!(report.to_string(), "while parsing the input");
+            assert_eq!(
+                report.root_cause().to_string(),
+                "invalid digit found in string"
+            );
+            let report = question.test_proposal(&1).await.unwrap_err();
+            assert_eq!(report.to_string(), "while validating the value");
+            assert_eq!(report.root_cause().to_string(), "Too small.");
+        });
+        assert_eq!(written(&out), "invalid digit found in string\nToo small.\n");
+    }
+
+    #[test]
+    fn select_labeled() {
+        let out = output();
+        let answer = crate::select_labeled(vec![("United States", "US"), ("Chile", "CL")])
+            .reader("Mexico\nUnited States\n".as_bytes())
+            .writer(out.clone())
+            .ask_and_wait()
+            .unwrap();
+        assert_eq!(answer, "US");
+        assert_eq!(
+            written(&out),
+            "1) United States\n2) Chile\nInput `Mexico` is not one of the options.\n"
+        );
+    }
+
+    #[test]
+    fn prompt_affixes() {
+        let out = output();
+        crate::text()
+            .reader("\nAna\n".as_bytes())
+            .writer(out.clone())
+            .repeat_message("Name")
+            .prompt_prefix("> ")
+            .prompt_suffix(": ")
+            .min_length_with_msg(1, "Empty.")
+            .ask_and_wait()
+            .unwrap();
+        assert_eq!(written(&out), "> Name: Empty.\n> Name: ");
+    }
+
+    #[test]
+    fn validate_config() {
+        let question = || QuestionBuilder::<u8, _, _>::new_fromstr("".as_bytes(), output());
+        assert_eq!(question().validate_config(), Ok(()));
+        assert_eq!(
+            question().attempts(0).validate_config(),
+            Err(ConfigError::NoAttempts)
+        );
+        assert_eq!(
+            question().timeout(Duration::ZERO).validate_config(),
+            Err(ConfigError::NoTime)
+        );
+        assert_eq!(
+            question().required().default_value(1).validate_config(),
+            Err(ConfigError::RequiredWithDefault)
+        );
+        assert_eq!(
+            question()
+                .required()
+                .default_value(1)
+                .attempts_then_default()
+                .validate_config(),
+            Ok(())
+        );
+
+        async_std::task::block_on(async {
+            let mut question = question().required();
+            assert_eq!(
+                question.validate_config_with_reader().await,
+                Err(ConfigError::RequiredAtEof)
+            );
+            let mut question =
+                QuestionBuilder::<u8, _, _>::new_fromstr("7\n".as_bytes(), output()).required();
+            assert_eq!(question.validate_config_with_reader().await, Ok(()));
+            assert_eq!(question.ask().await.unwrap(), 7);
+        });
+    }
+
+    #[test]
+    fn terminator_line() {
+        let answer = crate::text()
+            .reader("first\nsecond\nthird\nEND\nafter\n".as_bytes())
+            .writer(output())
+            .terminator_line("END")
+            .ask_and_wait()
+            .unwrap();
+        assert_eq!(answer, "first\nsecond\nthird");
+
+        let answer = crate::text()
+            .reader("first\nsecond".as_bytes())
+            .writer(output())
+            .terminator_line("END")
+            .ask_and_wait()
+            .unwrap();
+        assert_eq!(answer, "first\nsecond");
+    }
+
+    #[test]
+    fn no_feedback_on_default() {
+        let out = output();
+        let answer = crate::yn()
+            .reader("\n".as_bytes())
+            .writer(out.clone())
+            .default_value(true)
+            .feedback(|_| "Super!".to_string())
+            .ask_and_wait()
+            .unwrap();
+        assert!(answer);
+        assert_eq!(written(&out), "");
+    }
+
+    #[test]
+    fn transform_input_async() {
+        let answer = crate::text()
+            .reader("hello\n".as_bytes())
+            .writer(output())
+            .transform_input_async(|input| async move {
+                async_std::task::sleep(Duration::from_millis(10)).await;
+                input.to_uppercase()
+            })
+            .ask_and_wait()
+            .unwrap();
+        assert_eq!(answer, "HELLO");
+    }
+
+    #[test]
+    fn live_preview() {
+        let out = output();
+        let answer = crate::text()
+            .reader("ab\n".as_bytes())
+            .writer(out.clone())
+            .live_preview(|s| format!("[{}]", s))
+            .ask_and_wait()
+            .unwrap();
+        assert_eq!(answer, "ab");
+        assert_eq!(written(&out), "");
+
+        let (mut reader, out) = ("ab\x7fc\x1b[D\r".as_bytes(), output());
+        let (mut writer, mut bytes) = (out.clone(), Vec::new());
+        let echo = line_editor::Echo::Preview(Arc::new(|s: &str| format!("[{}]", s)));
+        let read =
+            line_editor::read_line(&mut reader, &mut writer, b'\n', &mut bytes, &echo, "", None);
+        async_std::task::block_on(read).unwrap().unwrap();
+        assert_eq!(bytes, b"ac\n");
+        let written = written(&out);
+        assert!(written.contains("\r\n[a]"));
+        assert!(written.contains("\r\n[ac]"));
+        assert!(written.ends_with("ac\r\n"));
+    }
+
+    #[test]
+    fn last_line_without_newline() {
+        let answer = crate::yn()
+            .reader("y".as_bytes())
+            .writer(output())
+            .ask_and_wait()
+            .unwrap();
+        assert!(answer);
+
+        let answer = crate::yn()
+            .reader("maybe\nn".as_bytes())
+            .writer(output())
+            .ask_and_wait()
+            .unwrap();
+        assert!(!answer);
+    }
+
+    #[test]
+    fn lex_range() {
+        let answer = crate::text()
+            .reader("banana\n".as_bytes())
+            .writer(output())
+            .lex_range("a", "m")
+            .ask_and_wait()
+            .unwrap();
+        assert_eq!(answer, "banana");
+
+        let out = output();
+        let answer = crate::text()
+            .reader("zebra\n".as_bytes())
+            .writer(out.clone())
+            .lex_range("a", "m")
+            .ask_and_wait();
+        assert!(matches!(answer, Err(ProcessingError::Eof)));
+        assert_eq!(
+            written(&out),
+            "The value must be alphabetically between a and m.\n"
+        );
+
+        let answer = crate::text()
+            .reader("Banana\nbanana\n".as_bytes())
+            .writer(output())
+            .lex_range("a", "m")
+            .ask_and_wait()
+            .unwrap();
+        assert_eq!(answer, "banana");
+
+        let answer = crate::text()
+            .reader("Banana\n".as_bytes())
+            .writer(output())
+            .lex_range_ignore_case("a", "m")
+            .ask_and_wait()
+            .unwrap();
+        assert_eq!(answer, "Banana");
+
+        let descriptor = crate::text().lex_range_ignore_case("A", "M").describe();
+        assert_eq!(descriptor.min, Some("A".to_string()));
+        assert_eq!(descriptor.max, Some("M".to_string()));
+    }
+
+    #[test]
+    fn session_budget() {
+        let answer = crate::question::<u8>()
+            .reader("first\nsecond\nthird\n42\n".as_bytes())
+            .writer(output())
+            .session_budget(16)
+            .ask_and_wait();
+        assert!(matches!(
+            answer,
+            Err(ProcessingError::InputTooLong { limit: 16 })
+        ));
+
+        let answer = crate::question::<u8>()
+            .reader("first\n42\n".as_bytes())
+            .writer(output())
+            .session_budget(16)
+            .ask_and_wait()
+            .unwrap();
+        assert_eq!(answer, 42);
+    }
+
+    #[test]
+    fn reset_attempts_on() {
+        let answer = crate::question::<u8>()
+            .reader("1\n12\n2\n11\n3\n7\n".as_bytes())
+            .writer(output())
+            .attempts(2)
+            .test(|value| *value == 7)
+            .reset_attempts_on(|value| *value > 10)
+            .ask_and_wait()
+            .unwrap();
+        assert_eq!(answer, 7);
+
+        let answer = crate::question::<u8>()
+            .reader("1\n12\n2\n3\n7\n".as_bytes())
+            .writer(output())
+            .attempts(2)
+            .test(|value| *value == 7)
+            .reset_attempts_on(|value| *value > 10)
+            .ask_and_wait();
+        assert!(matches!(answer, Err(ProcessingError::NoMoreAttempts)));
+    }
+
+    #[test]
+    fn required_toggle() {
+        let question = crate::text().required_toggle();
+        assert!(question.config.required);
+        let question = question.required_toggle();
+        assert!(!question.config.required);
+
+        #[allow(deprecated)]
+        let question = question.required_toogle();
+        assert!(question.config.required);
+    }
+
+    #[test]
+    fn capture_parse() {
+        let answer = crate::question::<u8>()
+            .reader("many\n".as_bytes())
+            .writer(output())
+            .capture_parse()
+            .ask_and_wait()
+            .unwrap();
+        assert_eq!(answer, Err("many".to_string()));
+
+        let answer = crate::question::<u8>()
+            .reader("42\n".as_bytes())
+            .writer(output())
+            .capture_parse()
+            .ask_and_wait()
+            .unwrap();
+        assert_eq!(answer, Ok(42));
+    }
+
+    #[test]
+    fn ask_with_reader() {
+        let (answer, (reader, buffered)) = async_std::task::block_on(
+            crate::question::<u8>()
+                .reader("42\nnext message".as_bytes())
+                .writer(output())
+                .ask_with_reader(),
+        );
+        assert_eq!(answer.unwrap(), 42);
+        assert_eq!(reader, b"");
+        assert_eq!(buffered, b"next message");
+    }
+
+    #[test]
+    fn pause() {
+        let out = output();
+        let reader = "whatever\nnext\n".as_bytes();
+        let (answer, (_, buffered)) = async_std::task::block_on(
+            crate::pause()
+                .reader(reader)
+                .writer(out.clone())
+                .ask_with_reader(),
+        );
+        assert!(answer.is_ok());
+        assert_eq!(buffered, b"next\n");
+        assert_eq!(written(&out), "Press Enter to continue...");
+    }
+
+    #[test]
+    fn validate() {
+        let question = crate::question::<u8>()
+            .str_test_with_msg(|s| !s.starts_with('0'), "No leading zeros.")
+            .min_max_with_msg(1, 10, "Out of range.");
+        assert_eq!(question.validate("7\n").unwrap(), 7);
+
+        let error = question.validate("42").unwrap_err();
+        assert_eq!(error.to_string(), "while validating the value");
+        assert_eq!(error.root_cause().to_string(), "Out of range.");
+
+        let error = question.validate("07").unwrap_err();
+        assert_eq!(error.root_cause().to_string(), "No leading zeros.");
+        assert!(question.validate("seven").is_err());
+    }
+
+    #[test]
+    fn feedback_link() {
+        let out = output();
+        crate::yn()
+            .reader("yes\n".as_bytes())
+            .writer(out.clone())
+            .feedback(|_| "Done! ".to_string())
+            .feedback_link("Docs", "https://docs.rs/asking")
+            .ask_and_wait()
+            .unwrap();
+        assert_eq!(written(&out), "Done! Docs (https://docs.rs/asking)\n");
+
+        let out = output();
+        crate::yn()
+            .reader("yes\n".as_bytes())
+            .writer(out.clone())
+            .terminal(true)
+            .feedback_link("Docs", "https://docs.rs/asking")
+            .ask_and_wait()
+            .unwrap();
+        assert_eq!(
+            written(&out),
+            "\x1b]8;;https://docs.rs/asking\x1b\\Docs\x1b]8;;\x1b\\\n"
+        );
+    }
+
+    #[test]
+    fn secret_from_path() {
+        let path = std::env::temp_dir().join(format!("asking-secret-{}", std::process::id()));
+        std::fs::write(&path, "hunter2\n").unwrap();
+        let out = output();
+        let answer = crate::password()
+            .reader("ignored\n".as_bytes())
+            .writer(out.clone())
+            .message("Password: ")
+            .secret_from_path(&path)
+            .ask_and_wait()
+            .unwrap();
+        assert_eq!(answer, "hunter2");
+        assert_eq!(written(&out), "");
+
+        let answer = crate::password()
+            .reader("".as_bytes())
+            .writer(output())
+            .min_length(10)
+            .secret_from_path(&path)
+            .ask_and_wait();
+        assert!(matches!(answer, Err(ProcessingError::InvalidSecret { .. })));
+        std::fs::remove_file(&path).unwrap();
+
+        let answer = crate::password()
+            .reader("".as_bytes())
+            .writer(output())
+            .secret_from_path(&path)
+            .ask_and_wait();
+        assert!(matches!(
+            answer,
+            Err(ProcessingError::SecretUnreadable { .. })
+        ));
+    }
+
+    #[test]
+    fn timeout_duration() {
+        let question = || {
+            crate::yn()
+                .reader(Delayed::new("yes\n".as_bytes(), Duration::from_millis(100)))
+                .writer(output())
+        };
+        let durations = [
+            question().timeout(Duration::from_millis(50)).ask_and_wait(),
+            question()
+                .timeout_input_only(Duration::from_millis(50))
+                .ask_and_wait(),
+        ];
+        for result in durations {
+            match result {
+                Err(ProcessingError::Timeout { duration, .. }) => {
+                    assert_eq!(duration, Duration::from_millis(50))
+                }
+                other => panic!("expected a timeout, got {:?}", other),
+            }
+        }
+    }
+
+    #[test]
+    fn password_policy() {
+        let out = output();
+        let answer = crate::text()
+            .reader("abc\nAbcdefg1!\n".as_bytes())
+            .writer(out.clone())
+            .password_policy(8, true, true, true)
+            .str_test_mode(TestMode::AllFailures)
+            .ask_and_wait()
+            .unwrap();
+        assert_eq!(answer, "Abcdefg1!");
+        assert_eq!(
+            written(&out),
+            "The password needs at least 8 characters.\n\
+            The password needs an uppercase letter.\n\
+            The password needs a digit.\n\
+            The password needs a symbol.\n"
+        );
+
+        let out = output();
+        crate::text()
+            .reader("abcdefgh\n".as_bytes())
+            .writer(out.clone())
+            .password_policy(8, false, true, false)
+            .str_test_mode(TestMode::AllFailures)
+            .ask_and_wait()
+            .unwrap_err();
+        assert_eq!(written(&out), "The password needs a digit.\n");
+    }
+
+    #[test]
+    fn text_preparsed() {
+        let answer = crate::text()
+            .reader("  spaced \tout \n".as_bytes())
+            .writer(output())
+            .ask_and_wait()
+            .unwrap();
+        assert_eq!(answer, "  spaced \tout");
+
+        let answer = crate::text()
+            .reader("unchanged\n".as_bytes())
+            .writer(output())
+            .preparser(|s| s.to_uppercase())
+            .ask_and_wait()
+            .unwrap();
+        assert_eq!(answer, "UNCHANGED\n");
+    }
+
+    #[test]
+    fn sticky_header() {
+        let question = |terminal: bool, out: Output| {
+            crate::question::<u8>()
+                .reader("one\ntwo\n3\n".as_bytes())
+                .writer(out)
+                .terminal(terminal)
+                .sticky_header("Step 1 of 3\n")
+                .repeat_message("Number: ")
+                .ask_and_wait()
+                .unwrap()
+        };
+
+        let out = output();
+        assert_eq!(question(false, out.clone()), 3);
+        assert_eq!(written(&out), "Step 1 of 3\nNumber: Number: Number: ");
+
+        let out = output();
+        assert_eq!(question(true, out.clone()), 3);
+        assert_eq!(written(&out), "Step 1 of 3\nNumber: ".repeat(3));
+    }
+
+    #[test]
+    fn into_parts() {
+        let question = || {
+            crate::question::<u8>()
+                .reader("big\n42\n7\n".as_bytes())
+                .message("Number: ")
+                .help("Use a small number.\n")
+                .max_with_msg(10, "Too big.")
+                .feedback(|n| format!("Got {}.\n", n))
+        };
+
+        let out = output();
+        let answer = question().writer(out.clone()).ask_and_wait().unwrap();
+        let expected = written(&out);
+
+        let out = output();
+        let parts = question().writer(out.clone()).into_parts();
+        let answer_from_parts = QuestionBuilder::from_parts(parts).ask_and_wait().unwrap();
+        assert_eq!(answer_from_parts, answer);
+        assert_eq!(written(&out), expected);
+
+        let out = output();
+        let mut parts = question().writer(out.clone()).into_parts();
+        parts.config.error_formatter = Arc::new(|s| format!("Error: {}\n", s));
+        QuestionBuilder::from_parts(parts).ask_and_wait().unwrap();
+        assert!(written(&out).contains("Error: Too big.\n"));
+    }
+
+    #[test]
+    fn terminal_mode_only_for_stdin() {
+        assert!(crate::text().config.reads_stdin);
+        let question = crate::text().reader("".as_bytes()).terminal(true);
+        assert!(!question.config.reads_stdin);
+    }
+
+    #[test]
+    fn mask_length_indicator() {
+        let out = output();
+        let answer = crate::text()
+            .reader("ab\x7fcd\r".as_bytes())
+            .writer(out.clone())
+            .terminal(true)
+            .mask()
+            .mask_length_indicator()
+            .ask_and_wait()
+            .unwrap();
+        assert_eq!(answer, "acd");
+        let displayed = written(&out);
+        let indicators: Vec<&str> = displayed
+            .split("\x1b8\x1b[J")
+            .filter_map(|redraw| redraw.split('\x1b').next())
+            .filter(|shown| !shown.is_empty())
+            .collect();
+        assert_eq!(
+            indicators,
+            [
+                "(1 chars)",
+                "(2 chars)",
+                "(1 chars)",
+                "(2 chars)",
+                "(3 chars)",
+                "\r\n"
+            ]
+        );
+        assert!(!displayed.contains("acd"));
+
+        let out = output();
+        crate::text()
+            .reader("secret\n".as_bytes())
+            .writer(out.clone())
+            .mask()
+            .mask_length_indicator()
+            .ask_and_wait()
+            .unwrap();
+        assert_eq!(written(&out), "");
+    }
+
+    #[test]
+    fn locale() {
+        let answer = crate::yn()
+            .reader("Ja\n".as_bytes())
+            .writer(output())
+            .locale(Locale::German)
+            .ask_and_wait()
+            .unwrap();
+        assert!(answer);
+    }
+
+    #[test]
+    fn dry_run() {
+        let out = output();
+        let (answer, (reader, unread)) = async_std::task::block_on(
+            crate::text()
+                .message("Name? ")
+                .default_value("anonymous".to_string())
+                .feedback(|name| format!("Hello {}!\n", name))
+                .dry_run()
+                .reader("Ferris\n".as_bytes())
+                .writer(out.clone())
+                .ask_with_reader(),
+        );
+        assert_eq!(answer.unwrap(), "anonymous");
+        assert_eq!(written(&out), "Name? Hello anonymous!\n");
+        assert_eq!(reader, b"Ferris\n");
+        assert!(unread.is_empty());
+
+        let result = crate::text()
+            .dry_run()
+            .reader("Ferris\n".as_bytes())
+            .writer(output())
+            .ask_and_wait();
+        assert!(matches!(result, Err(ProcessingError::NoDefault)));
+    }
+
+    #[test]
+    fn accept_if() {
+        let out = output();
+        let answer = crate::question()
+            .reader("0\n".as_bytes())
+            .writer(out.clone())
+            .accept_if(|code: &i32| *code == 0)
+            .min_max(1, 10)
+            .ask_and_wait()
+            .unwrap();
+        assert_eq!(answer, 0);
+        assert_eq!(written(&out), "");
+
+        let answer = crate::question()
+            .reader("11\n5\n".as_bytes())
+            .writer(output())
+            .accept_if(|code: &i32| *code == 0)
+            .min_max(1, 10)
+            .ask_and_wait()
+            .unwrap();
+        assert_eq!(answer, 5);
+    }
+
+    #[test]
+    #[allow(clippy::approx_constant)]
+    fn round_to() {
+        let out = output();
+        let answer = crate::question::<f64>()
+            .reader("3.14159\n".as_bytes())
+            .writer(out.clone())
+            .round_to(2)
+            .feedback(|pi| format!("{}\n", pi))
+            .ask_and_wait()
+            .unwrap();
+        assert_eq!(answer, 3.14);
+        assert_eq!(written(&out), "3.14\n");
+
+        let answer = crate::question::<f64>()
+            .reader("3.14159\n3.1415\n".as_bytes())
+            .writer(output())
+            .max(3.1415)
+            .round_to(2)
+            .ask_and_wait()
+            .unwrap();
+        assert_eq!(answer, 3.14);
+
+        let answer = crate::question::<f32>()
+            .reader("2.5\n".as_bytes())
+            .writer(output())
+            .round_to(0)
+            .ask_and_wait()
+            .unwrap();
+        assert_eq!(answer, 3.0);
+
+        for decimals in [15, 400, u32::MAX] {
+            let answer = crate::question::<f64>()
+                .reader("1.5\n".as_bytes())
+                .writer(output())
+                .round_to(decimals)
+                .ask_and_wait()
+                .unwrap();
+            assert_eq!(answer, 1.5);
+        }
+
+        let answer = crate::question::<f64>()
+            .reader("1e308\n".as_bytes())
+            .writer(output())
+            .round_to(2)
+            .ask_and_wait()
+            .unwrap();
+        assert_eq!(answer, 1e308);
+    }
+
+    #[test]
+    fn ask_into() {
+        let mut numbers = Vec::new();
+        let out = output();
+        let pushed = async_std::task::block_on(
+            crate::question::<u8>()
+                .message("Number? ")
+                .reader("1\n2\n3\n".as_bytes())
+                .writer(out.clone())
+                .ask_into(&mut numbers),
+        )
+        .unwrap();
+        assert_eq!(pushed, 3);
+        assert_eq!(numbers, vec![1, 2, 3]);
+        assert_eq!(written(&out), "Number? ".repeat(4));
+
+        let result = crate::question::<u8>()
+            .reader("stop\n".as_bytes())
+            .writer(output())
+            .abort_keyword("stop")
+            .ask_and_wait();
+        assert!(matches!(result, Err(ProcessingError::Aborted)));
+    }
+
+    #[test]
+    fn prefill() {
+        let out = output();
+        let answer = crate::text()
+            .message("Name? ")
+            .prefill("Ferris")
+            .reader("Crab\n".as_bytes())
+            .writer(out.clone())
+            .ask_and_wait()
+            .unwrap();
+        assert_eq!(answer, "Crab");
+        assert_eq!(written(&out), "Name? ");
+    }
+
+    #[test]
+    fn feedback_newline() {
+        let out = output();
+        crate::question::<u8>()
+            .reader("7\n".as_bytes())
+            .writer(out.clone())
+            .feedback(|n| format!("Got {}", n))
+            .feedback_newline()
+            .ask_and_wait()
+            .unwrap();
+        assert_eq!(written(&out), "Got 7\n");
+
+        let out = output();
+        crate::question::<u8>()
+            .reader("7\n".as_bytes())
+            .writer(out.clone())
+            .feedback_newline()
+            .ask_and_wait()
+            .unwrap();
+        assert_eq!(written(&out), "");
+    }
+
+    #[test]
+    fn combine() {
+        let out = output();
+        let (start, end) = async_std::task::block_on(crate::combine_with_msg(
+            QuestionBuilder::new_fromstr("5\n".as_bytes(), output()),
+            QuestionBuilder::new_fromstr("3\n5\n7\n".as_bytes(), out.clone()),
+            |start: &u32, end: &u32| end > start,
+            "The end must come after the start.",
+        ))
+        .unwrap();
+        assert_eq!((start, end), (5, 7));
+        assert_eq!(
+            written(&out),
+            "The end must come after the start.\n".repeat(2)
+        );
+    }
+
+    #[test]
+    fn parse_error_uses_default() {
+        let answer = crate::question::<u8>()
+            .reader("garbage\n".as_bytes())
+            .writer(output())
+            .default_value(42)
+            .parse_error_uses_default()
+            .ask_and_wait()
+            .unwrap();
+        assert_eq!(answer, 42);
+
+        let answer = crate::question::<u8>()
+            .reader("garbage\n7\n".as_bytes())
+            .writer(output())
+            .parse_error_uses_default()
+            .ask_and_wait()
+            .unwrap();
+        assert_eq!(answer, 7);
+    }
+
+    #[test]
+    fn log_outcome() {
+        let log = output();
+        crate::question::<u8>()
+            .reader("none\n7\n".as_bytes())
+            .writer(output())
+            .log_outcome(log.clone())
+            .ask_and_wait()
+            .unwrap();
+        assert_eq!(written(&log), "outcome=success attempts=2 value=7\n");
+
+        let log = output();
+        crate::question::<u8>()
+            .reader("".as_bytes())
+            .writer(output())
+            .log_outcome(log.clone())
+            .ask_and_wait()
+            .unwrap_err();
+        assert_eq!(
+            written(&log),
+            "outcome=failure attempts=0 error=\"EOF reached while asking for input.\"\n"
+        );
+
+        let log = output();
+        crate::question::<u8>()
+            .reader("none\n7\n".as_bytes())
+            .writer(output())
+            .repeat_message("Number: ")
+            .repeat_help("A number between 0 and 255.\n")
+            .feedback(|_| "Thanks!\n".to_string())
+            .log_outcome(log.clone())
+            .ask_and_wait()
+            .unwrap();
+        assert_eq!(written(&log), "outcome=success attempts=2 value=7\n");
+    }
+
+    #[test]
+    fn inside_by() {
+        let out = output();
+        let answer = crate::question::<f64>()
+            .reader("0.4\n0.495\n".as_bytes())
+            .writer(out.clone())
+            .inside_by(
+                vec![0.25, 0.5],
+                |value, option| (value - option).abs() <= 0.01,
+                "Choose a quarter or a half.",
+            )
+            .ask_and_wait()
+            .unwrap();
+        assert_eq!(answer, 0.495);
+        assert_eq!(written(&out), "Choose a quarter or a half.\n");
+    }
+
+    #[test]
+    fn assert_options_valid() {
+        let question = crate::select_listed(vec![1, 2, 3]).not(2);
+        assert_eq!(
+            question.assert_options_valid(),
+            Err(ConfigError::InvalidOptions {
+                options: vec!["2".to_string()]
+            })
+        );
+        assert_eq!(
+            crate::select_listed(vec![1, 2, 3]).assert_options_valid(),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn async_test() {
+        let reports = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let progress = reports.clone();
+        let out = output();
+        let answer = crate::text()
+            .reader("admin\nferris\n".as_bytes())
+            .writer(out.clone())
+            .progress(move |message| progress.lock().unwrap().push(message.to_string()))
+            .async_test(|name: String, progress| async move {
+                progress.report("connecting...");
+                async_std::task::sleep(Duration::from_millis(1)).await;
+                progress.report("checking availability...");
+                match name.as_str() {
+                    "admin" => Err(eyre::eyre!("The name is taken.")),
+                    _ => Ok(()),
+                }
+            })
+            .ask_and_wait()
+            .unwrap();
+        assert_eq!(answer, "ferris");
+        assert_eq!(written(&out), "The name is taken.\n");
+        assert_eq!(
+            *reports.lock().unwrap(),
+            vec![
+                "connecting...",
+                "checking availability...",
+                "connecting...",
+                "checking availability...",
+            ]
+        );
+    }
+
+    #[test]
+    fn message_lazy() {
+        let out = output();
+        let answer = crate::question::<u8>()
+            .message("Number? ")
+            .message_lazy()
+            .default_value(7)
+            .dry_run()
+            .reader("".as_bytes())
+            .writer(out.clone())
+            .ask_and_wait()
+            .unwrap();
+        assert_eq!(answer, 7);
+        assert_eq!(written(&out), "");
+
+        let out = output();
+        crate::question::<u8>()
+            .message("Number? ")
+            .message_lazy()
+            .reader("7\n".as_bytes())
+            .writer(out.clone())
+            .ask_and_wait()
+            .unwrap();
+        assert_eq!(written(&out), "Number? ");
+    }
+
+    #[test]
+    fn nonempty_text() {
+        let out = output();
+        let answer = crate::nonempty_text()
+            .reader("   \n  hi  \n".as_bytes())
+            .writer(out.clone())
+            .ask_and_wait()
+            .unwrap();
+        assert_eq!(answer.as_str(), "hi");
+        assert_eq!(written(&out), "The input can not be blank.\n");
+    }
+
+    #[test]
+    fn buffered() {
+        /// Writer that keeps a snapshot of everything written at each flush.
+        #[derive(Default)]
+        struct Snapshots {
+            written: Vec<u8>,
+            snapshots: Arc<std::sync::Mutex<Vec<String>>>,
+        }
+
+        impl Write for Snapshots {
+            fn poll_write(
+                mut self: Pin<&mut Self>,
+                _: &mut std::task::Context<'_>,
+                buf: &[u8],
+            ) -> Poll<io::Result<usize>> {
+                self.written.extend_from_slice(buf);
+                Poll::Ready(Ok(buf.len()))
+            }
+
+            fn poll_flush(
+                self: Pin<&mut Self>,
+                _: &mut std::task::Context<'_>,
+            ) -> Poll<io::Result<()>> {
+                let snapshot = String::from_utf8_lossy(&self.written).into_owned();
+                self.snapshots.lock().unwrap().push(snapshot);
+                Poll::Ready(Ok(()))
+            }
+
+            fn poll_close(
+                self: Pin<&mut Self>,
+                cx: &mut std::task::Context<'_>,
+            ) -> Poll<io::Result<()>> {
+                self.poll_flush(cx)
+            }
+        }
+
+        let snapshots = || {
+            let writer = Snapshots::default();
+            let snapshots = writer.snapshots.clone();
+            crate::question::<u8>()
+                .repeat_message("Number? ")
+                .min(5)
+                .feedback(|n| format!("Got {}\n", n))
+                .buffered()
+                .reader("3\n7\n".as_bytes())
+                .writer(writer)
+                .ask_and_wait()
+                .unwrap();
+            let snapshots = snapshots.lock().unwrap().clone();
+            snapshots
+        };
+        let expected = vec![
+            "Number? ",
+            "Number? The value can not be so small.\nNumber? ",
+            "Number? The value can not be so small.\nNumber? Got 7\n",
+        ];
+        assert_eq!(snapshots(), expected);
+        assert_eq!(snapshots(), expected);
+    }
+
+    #[test]
+    fn cancel_on() {
+        let out = output();
+        let result = crate::question::<u8>()
+            .message("Number? ")
+            .cancel_on(async {})
+            .reader("7\n".as_bytes())
+            .writer(out.clone())
+            .ask_and_wait();
+        assert!(matches!(result, Err(ProcessingError::Cancelled)));
+        assert_eq!(written(&out), "");
+
+        let answer = crate::question::<u8>()
+            .cancel_on(std::future::pending())
+            .reader("7\n".as_bytes())
+            .writer(output())
+            .ask_and_wait()
+            .unwrap();
+        assert_eq!(answer, 7);
+
+        fn assert_sync<S: Sync>(_: &S) {}
+        let not_sync = std::cell::Cell::new(false);
+        let question = crate::question::<u8>()
+            .cancel_on(async move { not_sync.set(true) })
+            .reader("7\n".as_bytes())
+            .writer(output());
+        assert_sync(&question);
+        let result = question.ask_and_wait();
+        assert!(matches!(result, Err(ProcessingError::Cancelled)));
+    }
+
+    #[test]
+    fn number_in_range() {
+        let out = output();
+        let answer = crate::number_in_range(1, 100)
+            .reader("0\n101\n42\n".as_bytes())
+            .writer(out.clone())
+            .ask_and_wait()
+            .unwrap();
+        assert_eq!(answer, 42);
+        assert_eq!(
+            written(&out),
+            "The value must be between 1 and 100.\n\
+            Enter a number between 1 and 100.\n\
+            The value must be between 1 and 100.\n"
+        );
+    }
+
+    #[test]
+    fn keep_leading_whitespace() {
+        let answer = crate::text()
+            .keep_leading_whitespace()
+            .reader("  indented  \n".as_bytes())
+            .writer(output())
+            .ask_and_wait()
+            .unwrap();
+        assert_eq!(answer, "  indented  ");
+    }
+
+    #[test]
+    fn show_previous_input() {
+        let out = output();
+        let answer = crate::question::<u8>()
+            .repeat_message("Number? ")
+            .max(10)
+            .show_previous_input()
+            .reader("300\n30\n3\n".as_bytes())
+            .writer(out.clone())
+            .ask_and_wait()
+            .unwrap();
+        assert_eq!(answer, 3);
+        assert_eq!(
+            written(&out),
+            "Number? (you entered: 300)\n\
+            Number? The value can not be so big.\n\
+            (you entered: 30)\n\
+            Number? "
+        );
+    }
+
+    #[test]
+    fn ask_once() {
+        let out = output();
+        let result = async_std::task::block_on(
+            crate::question::<u8>()
+                .max(10)
+                .reader("30\n3\n".as_bytes())
+                .writer(out.clone())
+                .ask_once(),
+        );
+        match result {
+            Err(ProcessingError::Rejected { reason }) => assert_eq!(
+                reason,
+                "while validating the value: The value can not be so big."
+            ),
+            other => panic!("unexpected {:?}", other),
+        }
+        assert_eq!(written(&out), "The value can not be so big.\n");
+
+        let result = async_std::task::block_on(
+            crate::question::<u8>()
+                .reader("three\n".as_bytes())
+                .writer(output())
+                .ask_once(),
+        );
+        match result {
+            Err(ProcessingError::Rejected { reason }) => {
+                assert!(reason.starts_with("while parsing the input: "))
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+
+        let answer = async_std::task::block_on(
+            crate::question::<u8>()
+                .reader("3\n".as_bytes())
+                .writer(output())
+                .ask_once(),
+        );
+        assert_eq!(answer.unwrap(), 3);
+    }
+
+    #[test]
+    fn required_if() {
+        let ask = |has_company: bool| {
+            let mut questionnaire = crate::Questionnaire::new();
+            async_std::task::block_on(async {
+                let input = if has_company { "y\n" } else { "n\n" };
+                questionnaire
+                    .ask(
+                        "company",
+                        crate::yn().reader(input.as_bytes()).writer(output()),
+                    )
+                    .await
+                    .unwrap();
+                questionnaire
+                    .ask(
+                        "company_name",
+                        crate::text()
+                            .default_value("none".to_string())
+                            .required_if(|context| context.get("company") == Some(true))
+                            .reader("\nAcme\n".as_bytes())
+                            .writer(output()),
+                    )
+                    .await
+                    .unwrap()
+            })
+        };
+        assert_eq!(ask(true), "Acme");
+        assert_eq!(ask(false), "none");
+    }
+
+    #[test]
+    fn total_timeout() {
+        let mut questionnaire =
+            crate::Questionnaire::new().total_timeout(Duration::from_millis(150));
+        async_std::task::block_on(async {
+            let slow = || Delayed::new("1\n".as_bytes(), Duration::from_millis(50));
+            let first: u8 = questionnaire
+                .ask("first", QuestionBuilder::new_fromstr(slow(), output()))
+                .await
+                .unwrap();
+            assert_eq!(first, 1);
+            let second = questionnaire
+                .ask::<u8, _, _>("second", QuestionBuilder::new_fromstr(slow(), output()))
+                .await;
+            assert!(matches!(second, Err(ProcessingError::Timeout { .. })));
+        });
+    }
+
+    #[test]
+    fn duplex() {
+        /// In-memory connection, reading from `input` and writing to `output`.
+        #[derive(Clone)]
+        struct Connection {
+            input: async_dup::Arc<async_dup::Mutex<io::Cursor<Vec<u8>>>>,
+            output: Output,
+        }
+
+        impl Read for Connection {
+            fn poll_read(
+                self: Pin<&mut Self>,
+                cx: &mut std::task::Context<'_>,
+                buf: &mut [u8],
+            ) -> Poll<io::Result<usize>> {
+                Pin::new(&mut &*self.input).poll_read(cx, buf)
+            }
+        }
+
+        impl Write for Connection {
+            fn poll_write(
+                self: Pin<&mut Self>,
+                cx: &mut std::task::Context<'_>,
+                buf: &[u8],
+            ) -> Poll<io::Result<usize>> {
+                Pin::new(&mut &*self.output).poll_write(cx, buf)
+            }
+
+            fn poll_flush(
+                self: Pin<&mut Self>,
+                cx: &mut std::task::Context<'_>,
+            ) -> Poll<io::Result<()>> {
+                Pin::new(&mut &*self.output).poll_flush(cx)
+            }
+
+            fn poll_close(
+                self: Pin<&mut Self>,
+                cx: &mut std::task::Context<'_>,
+            ) -> Poll<io::Result<()>> {
+                Pin::new(&mut &*self.output).poll_close(cx)
+            }
+        }
+
+        let connection = Connection {
+            input: async_dup::Arc::new(async_dup::Mutex::new(io::Cursor::new(b"7\n".to_vec()))),
+            output: output(),
+        };
+        let answer: u8 = QuestionBuilder::duplex(connection.clone())
+            .message("Number? ")
+            .ask_and_wait()
+            .unwrap();
+        assert_eq!(answer, 7);
+        assert_eq!(written(&connection.output), "Number? ");
+    }
+
+    #[test]
+    fn message_template() {
+        let out = output();
+        let answer = crate::question::<u8>()
+            .message_template("Enter a value between {min} and {max} {unit}: ")
+            .template_vars(vec![("min", 1), ("max", 10)])
+            .reader("7\n".as_bytes())
+            .writer(out.clone())
+            .ask_and_wait()
+            .unwrap();
+        assert_eq!(answer, 7);
+        assert_eq!(written(&out), "Enter a value between 1 and 10 {unit}: ");
+    }
+
+    #[test]
+    fn input_encoding() {
+        let input: Vec<u8> = "\u{feff}true\n"
+            .encode_utf16()
+            .flat_map(u16::to_le_bytes)
+            .collect();
+        let answer = crate::yn()
+            .reader(input.as_slice())
+            .writer(output())
+            .input_encoding(Encoding::Utf16Le)
+            .ask_and_wait()
+            .unwrap();
+        assert!(answer);
+    }
+
+    #[test]
+    fn matches_hash() {
+        fn fnv1a(s: &str) -> u64 {
+            s.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+                (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+            })
+        }
+        let out = output();
+        let code = crate::text()
+            .matches_hash(0xc904_3737_b0d7_49a0, fnv1a)
+            .reader("123456\n482913\n".as_bytes())
+            .writer(out.clone())
+            .ask_and_wait()
+            .unwrap();
+        assert_eq!(code, "482913");
+        assert_eq!(written(&out), "The code is not correct.\n");
+    }
+
+    #[test]
+    fn ask_into_buffer() {
+        let out = output();
+        let mut question = crate::text()
+            .message("Name: ")
+            .attempts(2)
+            .test_with_msg(|s: &String| s != "root", "Not root.")
+            .reader("root\nroot\nalice\nroot\nbob\n".as_bytes())
+            .writer(out.clone());
+        let mut buffer = String::from("previous");
+        async_std::task::block_on(question.ask_into_buffer(&mut buffer)).unwrap_err();
+        assert_eq!(buffer, "previous");
+        async_std::task::block_on(question.ask_into_buffer(&mut buffer)).unwrap();
+        assert_eq!(buffer, "alice");
+        async_std::task::block_on(question.ask_into_buffer(&mut buffer)).unwrap();
+        assert_eq!(buffer, "bob");
+    }
+
+    #[test]
+    fn feedback_with_timing() {
+        let timing = Arc::new(std::sync::Mutex::new(None));
+        let recorded = timing.clone();
+        let out = output();
+        crate::text()
+            .reader(Delayed::new("ok\n".as_bytes(), Duration::from_millis(20)))
+            .writer(out.clone())
+            .feedback_with_timing(move |value, elapsed| {
+                *recorded.lock().unwrap() = Some(elapsed);
+                format!("Answered: {}\n", value)
+            })
+            .ask_and_wait()
+            .unwrap();
+        let elapsed = timing.lock().unwrap().unwrap();
+        assert!(elapsed >= Duration::from_millis(60));
+        assert!(elapsed < Duration::from_secs(5));
+        assert_eq!(written(&out), "Answered: ok\n");
+    }
+
+    #[test]
+    fn default_chain() {
+        let answer = crate::question::<u16>()
+            .default_chain(vec![
+                DefaultSource::Arg(None),
+                DefaultSource::Env("ASKING_TEST_DEFAULT_CHAIN_UNSET".to_string()),
+                DefaultSource::File("does/not/exist.txt".into()),
+                DefaultSource::Arg(Some("not a number".to_string())),
+                DefaultSource::Literal(8080),
+                DefaultSource::Literal(1),
+            ])
+            .reader("\n".as_bytes())
+            .writer(output())
+            .ask_and_wait()
+            .unwrap();
+        assert_eq!(answer, 8080);
+
+        let answer = crate::question::<u16>()
+            .default_chain(vec![
+                DefaultSource::Arg(Some("3000".to_string())),
+                DefaultSource::Literal(8080),
+            ])
+            .reader("\n".as_bytes())
+            .writer(output())
+            .ask_and_wait()
+            .unwrap();
+        assert_eq!(answer, 3000);
+
+        let path = std::env::temp_dir().join("asking_test_default_chain.txt");
+        std::fs::write(&path, " 4000\n").unwrap();
+        let answer = crate::question::<u16>()
+            .default_chain(vec![
+                DefaultSource::File(path.clone()),
+                DefaultSource::Literal(8080),
+            ])
+            .reader("\n".as_bytes())
+            .writer(output())
+            .ask_and_wait()
+            .unwrap();
+        std::fs::remove_file(path).unwrap();
+        assert_eq!(answer, 4000);
+    }
+
+    #[test]
+    #[allow(clippy::manual_is_multiple_of)]
+    fn test_blocking() {
+        let out = output();
+        let answer = crate::question::<u32>()
+            .test_blocking_with_msg(
+                |n: &u32| {
+                    std::thread::sleep(Duration::from_millis(20));
+                    n % 2 == 0
+                },
+                "Not even.",
+            )
+            .reader("3\n4\n".as_bytes())
+            .writer(out.clone())
+            .ask_and_wait()
+            .unwrap();
+        assert_eq!(answer, 4);
+        assert_eq!(written(&out), "Not even.\n");
+    }
+
+    #[test]
+    fn required_help_first() {
+        let ask = |question: StdQuestionBuilder<String>| {
+            let out = output();
+            question
+                .required_with_msg("Required!")
+                .help("Type your name.\n")
+                .reader("\nalice\n".as_bytes())
+                .writer(out.clone())
+                .ask_and_wait()
+                .unwrap();
+            written(&out)
+        };
+        assert_eq!(ask(crate::text()), "Type your name.\nRequired!\n");
+        assert_eq!(
+            ask(crate::text().required_help_first(false)),
+            "Required!\nType your name.\n"
+        );
+        assert_eq!(ask(crate::text().required_without_help()), "Required!\n");
+    }
+
+    #[test]
+    fn select_map() {
+        #[derive(Debug, Clone, PartialEq)]
+        enum Command {
+            Start,
+            Stop,
+        }
+
+        let out = output();
+        let answer = crate::select_map(vec![("start", Command::Start), ("stop", Command::Stop)])
+            .reader("pause\nstart\n".as_bytes())
+            .writer(out.clone())
+            .ask_and_wait()
+            .unwrap();
+        assert_eq!(answer, Command::Start);
+        assert_eq!(written(&out), "Input `pause` is not one of: start, stop.\n");
+    }
+
+    #[test]
+    fn unbuffered_reader() {
+        let (answer, (rest, buffered)) = async_std::task::block_on(
+            crate::text()
+                .unbuffered_reader()
+                .reader("hello\nnext line\n".as_bytes())
+                .writer(output())
+                .ask_with_reader(),
+        );
+        assert_eq!(answer.unwrap(), "hello");
+        assert_eq!(rest, b"next line\n");
+        assert!(buffered.is_empty());
+
+        let (_, (rest, buffered)) = async_std::task::block_on(
+            crate::text()
+                .reader("hello\nnext line\n".as_bytes())
+                .writer(output())
+                .ask_with_reader(),
+        );
+        assert!(rest.is_empty());
+        assert_eq!(buffered, b"next line\n");
+    }
+
+    #[test]
+    fn describe() {
+        let descriptor = crate::text()
+            .message("Name: ")
+            .help("Your \"nickname\".\n")
+            .required()
+            .max_length_with_msg(8, "Too long.")
+            .lex_range("a", "m")
+            .describe();
+        assert_eq!(
+            descriptor.to_json(),
+            r#"{"message":"Name: ","help":"Your \"nickname\".\n","required":true,"default":null,"options":[],"min_length":null,"max_length":8,"min":"a","max":"m"}"#
+        );
+
+        let descriptor = crate::text().max_length(8).describe();
+        assert_eq!(descriptor.min_length, None);
+        assert_eq!(descriptor.max_length, Some(8));
+        assert!(crate::text().max_length(8).validate("short").is_ok());
+
+        let descriptor = crate::text().min_length(2).describe();
+        assert_eq!(descriptor.min_length, Some(2));
+        assert_eq!(descriptor.max_length, None);
+        assert!(crate::text().min_length(2).validate("longer").is_ok());
+
+        let descriptor = crate::select_listed(vec![1, 2]).default_value(2).describe();
+        assert_eq!(descriptor.options, vec!["1", "2"]);
+        assert_eq!(descriptor.default.as_deref(), Some("2"));
+    }
+
+    #[test]
+    fn ignore_write_errors() {
+        struct Broken;
+
+        impl Write for Broken {
+            fn poll_write(
+                self: Pin<&mut Self>,
+                _: &mut std::task::Context<'_>,
+                _: &[u8],
+            ) -> Poll<io::Result<usize>> {
+                Poll::Ready(Err(io::ErrorKind::BrokenPipe.into()))
+            }
+            fn poll_flush(
+                self: Pin<&mut Self>,
+                _: &mut std::task::Context<'_>,
+            ) -> Poll<io::Result<()>> {
+                Poll::Ready(Err(io::ErrorKind::BrokenPipe.into()))
+            }
+            fn poll_close(
+                self: Pin<&mut Self>,
+                _: &mut std::task::Context<'_>,
+            ) -> Poll<io::Result<()>> {
+                Poll::Ready(Ok(()))
+            }
+        }
+
+        let answer = crate::question::<u8>()
+            .message("Number: ")
+            .help("A small number.\n")
+            .feedback(|_| "Thanks!\n".to_string())
+            .reader("big\n7\n".as_bytes())
+            .writer(Broken)
+            .ignore_write_errors()
+            .ask_and_wait()
+            .unwrap();
+        assert_eq!(answer, 7);
+
+        let result = crate::question::<u8>()
+            .message("Number: ")
+            .reader("7\n".as_bytes())
+            .writer(Broken)
+            .ask_and_wait();
+        assert!(matches!(result, Err(ProcessingError::Io { .. })));
+    }
+
+    #[test]
+    fn inside_range() {
+        let question = crate::question::<u32>().inside_range(5..=100);
+        assert!(question.validate("5").is_ok());
+        assert!(question.validate("100").is_ok());
+        assert!(question.validate("101").is_err());
+
+        let question = crate::question::<u32>().inside_range(5..100);
+        assert!(question.validate("99").is_ok());
+        assert!(question.validate("100").is_err());
+        assert!(question.validate("4").is_err());
+
+        let question = crate::question::<u64>().inside_range(1..u64::MAX);
+        assert!(question.validate("18446744073709551614").is_ok());
+        assert!(question.validate("18446744073709551615").is_err());
+        assert!(question.validate("0").is_err());
+    }
+
+    #[test]
+    fn help_after() {
+        let out = output();
+        let answer = crate::question::<u8>()
+            .repeat_message("> ")
+            .help("Enter a number below 256.\n")
+            .help_after(2)
+            .reader("a\nb\nc\nd\n7\n".as_bytes())
+            .writer(out.clone())
+            .ask_and_wait()
+            .unwrap();
+        assert_eq!(answer, 7);
+        assert_eq!(written(&out), "> > > Enter a number below 256.\n> > ");
+
+        let out = output();
+        crate::question::<u8>()
+            .repeat_help("Help!\n")
+            .help_after(1)
+            .reader("a\nb\nc\n7\n".as_bytes())
+            .writer(out.clone())
+            .ask_and_wait()
+            .unwrap();
+        assert_eq!(written(&out), "Help!\nHelp!\n");
+    }
+
+    #[test]
+    fn friendly_numeric_errors() {
+        let out = output();
+        let answer = crate::question::<u32>()
+            .friendly_numeric_errors()
+            .reader("-1\n+5\n1.5\n4294967296\n4294967295\n".as_bytes())
+            .writer(out.clone())
+            .ask_and_wait()
+            .unwrap();
+        assert_eq!(answer, u32::MAX);
+        assert_eq!(
+            written(&out),
+            "This must be a non-negative whole number.\n\
+             This must be a whole number without a sign, like 5.\n\
+             This must be a non-negative whole number.\n\
+             This must be at most 4294967295.\n"
+        );
+    }
+
+    #[test]
+    fn select_normalized() {
+        let out = output();
+        let answer = crate::select_normalized(vec!["Apple", "Banana"])
+            .reader("pear\n  aPPle \n".as_bytes())
+            .writer(out.clone())
+            .ask_and_wait()
+            .unwrap();
+        assert_eq!(answer, "Apple");
+        assert_eq!(written(&out), "Input `pear` is not one of the options.\n");
+    }
+
+    #[test]
+    fn input_encoding_idle_timeout() {
+        let question = || {
+            crate::text()
+                .reader("héllo\n".as_bytes())
+                .writer(output())
+                .input_encoding(Encoding::Utf16Le)
+                .idle_timeout(Duration::from_secs(5))
+        };
+        assert_eq!(
+            question().validate_config(),
+            Err(ConfigError::EncodingUnsupported)
+        );
+        let result = question().ask_and_wait();
+        assert!(matches!(result, Err(ProcessingError::Io { .. })));
+    }
+
+    #[test]
+    fn line_terminator_non_ascii() {
+        let answer = crate::text()
+            .reader(&b"one\xa7two\xa7"[..])
+            .writer(output())
+            .line_terminator(0xa7)
+            .ask_and_wait()
+            .unwrap();
+        assert_eq!(answer, "one");
+
+        let input: Vec<u8> = "one\u{a7}two"
+            .encode_utf16()
+            .flat_map(u16::to_be_bytes)
+            .collect();
+        let answer = crate::text()
+            .reader(input.as_slice())
+            .writer(output())
+            .input_encoding(Encoding::Utf16Be)
+            .line_terminator(0xa7)
+            .ask_and_wait()
+            .unwrap();
+        assert_eq!(answer, "one");
+    }
 }
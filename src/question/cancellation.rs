@@ -0,0 +1,40 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+/// Future that stops asking when it completes.
+///
+/// Related to the method [`cancel_on`].
+///
+/// [`cancel_on`]: struct.QuestionBuilder.html#method.cancel_on
+pub struct Cancellation(Pin<Box<dyn Future<Output = ()> + Send>>);
+
+impl Cancellation {
+    /// Wraps `future`, which does not need to be `Sync`.
+    pub fn new<F>(future: F) -> Self
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        Cancellation(Box::pin(future))
+    }
+}
+
+impl Future for Cancellation {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        self.get_mut().0.as_mut().poll(cx)
+    }
+}
+
+// SAFETY: the future is only reachable through `&mut Cancellation`, when polling,
+// so a shared `&Cancellation` gives no access to it from other threads.
+unsafe impl Sync for Cancellation {}
+
+impl std::fmt::Debug for Cancellation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Cancellation").finish_non_exhaustive()
+    }
+}
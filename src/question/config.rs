@@ -0,0 +1,190 @@
+use std::{collections::HashMap, path::PathBuf, sync::Arc, time::Duration};
+
+use super::{
+    AttemptsFeedback, Cancellation, Encoding, Executor, Message, Notify, Predicate, Preparser,
+    Preview, StrTest, TestMode, Transform,
+};
+use crate::Context;
+
+/// Settings of a [`QuestionBuilder`] that do not depend on the type of the value,
+/// nor on the reader and writer.
+///
+/// Part of the [`QuestionParts`]. Each field corresponds to the method with the same name.
+///
+/// [`QuestionBuilder`]: struct.QuestionBuilder.html
+/// [`QuestionParts`]: struct.QuestionParts.html
+#[non_exhaustive]
+pub struct QuestionConfig {
+    /// Message, given the attempt number.
+    pub message: Message,
+    /// Whether the message is displayed before every attempt.
+    pub repeat_message: bool,
+    /// Prefix of every line of the message, help and feedback.
+    pub indent: String,
+    /// Prefix and suffix of the message.
+    pub prompt_affixes: (String, String),
+    /// Help message.
+    pub help: String,
+    /// Whether the help is displayed after every failed attempt.
+    pub repeat_help: bool,
+    /// Number of failed attempts before the help is displayed.
+    pub help_after: usize,
+    /// What input counts as empty.
+    pub is_empty_when: Arc<dyn Fn(&str) -> bool + Send + Sync>,
+    /// Size of the chunks in which the feedback is displayed.
+    pub feedback_chunk_size: usize,
+    /// Asynchronous transformation of the raw input.
+    pub transform_input: Option<Transform>,
+    /// Preparsers, applied in order.
+    pub preparsers: Vec<Preparser>,
+    /// Tests over the unparsed input, and whether their errors are displayed.
+    pub str_tests: Vec<(StrTest, bool)>,
+    /// How many failing tests over the unparsed input are reported.
+    pub str_test_mode: TestMode,
+    /// Formatter of error messages.
+    pub error_formatter: Arc<dyn Fn(String) -> String + Send + Sync>,
+    /// How the question is timed.
+    pub executor: Executor,
+    /// Number of attempts, and the message displayed with the attempts left.
+    pub attempts: Option<(usize, AttemptsFeedback)>,
+    /// Whether the default value is returned when there are no more attempts.
+    pub attempts_then_default: bool,
+    /// Delay before every new attempt.
+    pub retry_delay: Duration,
+    /// Callback with the time the user took to answer.
+    pub on_timing: Option<Arc<dyn Fn(Duration) + Send + Sync>>,
+    /// Message displayed when empty input is not accepted.
+    pub required_message: String,
+    /// Whether input is required.
+    pub required: bool,
+    /// Whether the help is displayed before the required message, after it, or not at all.
+    pub required_help: Option<bool>,
+    /// Predicate of the previous answers that make the input required.
+    pub required_if: Option<Predicate<Context>>,
+    /// Options to choose from.
+    pub options: Vec<String>,
+    /// Byte that ends a line of input.
+    pub line_terminator: u8,
+    /// Line that ends the input.
+    pub terminator_line: Option<String>,
+    /// Message asking for the confirmation, and message upon a mismatch.
+    pub confirmation: Option<(String, String)>,
+    /// Number of times the confirmation is asked again.
+    pub confirm_retries: usize,
+    /// Answers of previous questions.
+    pub context: Context,
+    /// Whether the reader and writer are an interactive terminal.
+    pub terminal: bool,
+    /// Whether the reader is the standard input of the process, whose terminal mode can change.
+    pub(super) reads_stdin: bool,
+    /// Whether input is masked.
+    pub mask: bool,
+    /// Whether masked input requires a terminal.
+    pub require_tty_for_mask: bool,
+    /// Whether the number of characters of masked input is displayed.
+    pub mask_length_indicator: bool,
+    /// Preview of the input while typing it.
+    pub live_preview: Option<Preview>,
+    /// Total number of bytes that can be read.
+    pub session_budget: Option<usize>,
+    /// Text and URL of a link displayed after the feedback.
+    pub feedback_link: Option<(String, String)>,
+    /// File from which the answer is taken.
+    pub secret_from_path: Option<PathBuf>,
+    /// Header displayed above the message.
+    pub sticky_header: Option<String>,
+    /// Whether the default value is returned without reading input.
+    pub dry_run: bool,
+    /// Line that stops asking.
+    pub abort_keyword: Option<String>,
+    /// Text the input line starts with, in a terminal.
+    pub prefill: Option<String>,
+    /// Whether a newline is appended to the feedback.
+    pub feedback_newline: bool,
+    /// Whether the default value is returned when the input can not be parsed.
+    pub parse_error_uses_default: bool,
+    /// Callback with the progress reported by asynchronous tests.
+    pub progress: Option<Notify>,
+    /// Whether the message is displayed only if input is read.
+    pub message_lazy: bool,
+    /// Whether the output is flushed only before reading input and at the end.
+    pub buffered: bool,
+    /// Future that stops asking when it completes.
+    pub cancel_on: Option<Cancellation>,
+    /// Whether the previous input is displayed before every new attempt.
+    pub show_previous_input: bool,
+    /// Variables that replace the placeholders of the message.
+    pub template_vars: HashMap<String, String>,
+    /// Encoding of the input.
+    pub input_encoding: Encoding,
+    /// Whether the reader is read one byte at a time.
+    pub unbuffered_reader: bool,
+    /// Whether errors while writing are ignored.
+    pub ignore_write_errors: bool,
+    /// Minimum and maximum length of the input, as described by `describe`.
+    pub(super) length_bounds: (Option<usize>, Option<usize>),
+    /// Minimum and maximum value, as described by `describe`.
+    pub(super) value_bounds: (Option<String>, Option<String>),
+}
+
+impl Default for QuestionConfig {
+    fn default() -> Self {
+        Self {
+            message: Arc::new(|_| String::default()),
+            repeat_message: false,
+            indent: String::default(),
+            prompt_affixes: (String::default(), String::default()),
+            help: String::default(),
+            repeat_help: false,
+            help_after: 0,
+            is_empty_when: Arc::new(str::is_empty),
+            feedback_chunk_size: 4096,
+            transform_input: None,
+            preparsers: vec![Arc::new(|s| s.trim_end().to_string())],
+            str_tests: Vec::default(),
+            str_test_mode: TestMode::default(),
+            error_formatter: Arc::new(|s| s + "\n"),
+            executor: Executor::None,
+            attempts: None,
+            attempts_then_default: false,
+            retry_delay: Duration::default(),
+            on_timing: None,
+            required_message: String::default(),
+            required: false,
+            required_help: Some(true),
+            required_if: None,
+            options: Vec::default(),
+            line_terminator: b'\n',
+            terminator_line: None,
+            confirmation: None,
+            confirm_retries: 2,
+            context: Context::default(),
+            terminal: false,
+            reads_stdin: false,
+            mask: false,
+            require_tty_for_mask: false,
+            mask_length_indicator: false,
+            live_preview: None,
+            session_budget: None,
+            feedback_link: None,
+            secret_from_path: None,
+            sticky_header: None,
+            dry_run: false,
+            abort_keyword: None,
+            prefill: None,
+            feedback_newline: false,
+            parse_error_uses_default: false,
+            progress: None,
+            message_lazy: false,
+            buffered: false,
+            cancel_on: None,
+            show_previous_input: false,
+            template_vars: HashMap::new(),
+            input_encoding: Encoding::default(),
+            unbuffered_reader: false,
+            ignore_write_errors: false,
+            length_bounds: (None, None),
+            value_bounds: (None, None),
+        }
+    }
+}
@@ -0,0 +1,38 @@
+use std::path::PathBuf;
+
+use super::Parser;
+
+/// Source of a default value, in a chain of fallbacks.
+///
+/// Related to the method [`default_chain`].
+///
+/// [`default_chain`]: struct.QuestionBuilder.html#method.default_chain
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum DefaultSource<T> {
+    /// A command-line argument, if it was given.
+    Arg(Option<String>),
+    /// An environment variable, by name.
+    Env(String),
+    /// A file, whose content is taken without surrounding whitespace.
+    ///
+    /// The file is read asynchronously. If it can not be read,
+    /// the next source is tried, without reporting the error.
+    File(PathBuf),
+    /// A value.
+    Literal(T),
+}
+
+impl<T: Clone> DefaultSource<T> {
+    /// Returns the value of this source, if there is one and it can be parsed.
+    pub(crate) async fn resolve(&self, parser: &Parser<T>) -> Option<T> {
+        match self {
+            DefaultSource::Arg(arg) => parser(arg.as_ref()?).ok(),
+            DefaultSource::Env(name) => parser(&std::env::var(name).ok()?).ok(),
+            DefaultSource::File(path) => {
+                let content = async_std::fs::read_to_string(path).await.ok()?;
+                parser(content.trim()).ok()
+            }
+            DefaultSource::Literal(value) => Some(value.clone()),
+        }
+    }
+}
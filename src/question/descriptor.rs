@@ -0,0 +1,91 @@
+/// Machine-readable description of a question, obtained by [`describe`].
+///
+/// Useful to render the question natively, for example, in a GUI or web front-end,
+/// and then check the answer with [`validate`].
+///
+/// Constraints are only known when they are set by methods that record them,
+/// like [`min_length`] or the pattern [`number_in_range`],
+/// and not for arbitrary tests.
+///
+/// [`describe`]: struct.QuestionBuilder.html#method.describe
+/// [`validate`]: struct.QuestionBuilder.html#method.validate
+/// [`min_length`]: struct.QuestionBuilder.html#method.min_length
+/// [`number_in_range`]: fn.number_in_range.html
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct PromptDescriptor {
+    /// Message of the first attempt.
+    pub message: String,
+    /// Help message.
+    pub help: String,
+    /// Whether input is required.
+    pub required: bool,
+    /// Default value, as a string.
+    pub default: Option<String>,
+    /// Options to choose from.
+    pub options: Vec<String>,
+    /// Minimum length of the input.
+    pub min_length: Option<usize>,
+    /// Maximum length of the input.
+    pub max_length: Option<usize>,
+    /// Minimum value, as a string.
+    pub min: Option<String>,
+    /// Maximum value, as a string.
+    pub max: Option<String>,
+}
+
+impl PromptDescriptor {
+    /// Serializes the descriptor as a JSON object.
+    ///
+    /// Missing values are `null`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let descriptor = asking::text().message("Name: ").required().describe();
+    /// assert_eq!(
+    ///     descriptor.to_json(),
+    ///     r#"{"message":"Name: ","help":"","required":true,"default":null,"options":[],"min_length":null,"max_length":null,"min":null,"max":null}"#
+    /// );
+    /// ```
+    pub fn to_json(&self) -> String {
+        let options: Vec<String> = self
+            .options
+            .iter()
+            .map(|option| json_string(option))
+            .collect();
+        format!(
+            r#"{{"message":{},"help":{},"required":{},"default":{},"options":[{}],"min_length":{},"max_length":{},"min":{},"max":{}}}"#,
+            json_string(&self.message),
+            json_string(&self.help),
+            self.required,
+            json_option(self.default.as_deref().map(json_string)),
+            options.join(","),
+            json_option(self.min_length),
+            json_option(self.max_length),
+            json_option(self.min.as_deref().map(json_string)),
+            json_option(self.max.as_deref().map(json_string)),
+        )
+    }
+}
+
+fn json_option<V: ToString>(value: Option<V>) -> String {
+    value.map_or_else(|| "null".to_string(), |value| value.to_string())
+}
+
+fn json_string(text: &str) -> String {
+    let mut json = String::with_capacity(text.len() + 2);
+    json.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if c.is_control() => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
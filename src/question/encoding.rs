@@ -0,0 +1,113 @@
+use async_std::io::{
+    prelude::{BufReadExt, ReadExt},
+    BufRead,
+};
+use std::{io, marker::Unpin};
+
+/// Text encoding of the input.
+///
+/// Related to the method [`input_encoding`].
+///
+/// [`input_encoding`]: struct.QuestionBuilder.html#method.input_encoding
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Encoding {
+    /// UTF-8, the usual encoding of terminals and files.
+    #[default]
+    Utf8,
+    /// UTF-16, little endian, common for files written on Windows.
+    Utf16Le,
+    /// UTF-16, big endian.
+    Utf16Be,
+    /// ISO-8859-1, where each byte is a character.
+    Latin1,
+}
+
+impl Encoding {
+    /// Reads until `terminator`, like `read_until`, but reading whole code units.
+    ///
+    /// In UTF-16, the terminator is the code unit with value `terminator`.
+    #[allow(clippy::manual_is_multiple_of)]
+    pub(crate) async fn read_until<R>(
+        self,
+        reader: &mut R,
+        terminator: u8,
+        bytes: &mut Vec<u8>,
+    ) -> io::Result<usize>
+    where
+        R: BufRead + Unpin,
+    {
+        let start = bytes.len();
+        loop {
+            if reader.read_until(terminator, bytes).await? == 0 {
+                break;
+            }
+            let length = bytes.len() - start;
+            match self {
+                Encoding::Utf8 | Encoding::Latin1 => break,
+                Encoding::Utf16Be if length % 2 == 0 && bytes[bytes.len() - 2] == 0 => break,
+                Encoding::Utf16Be => (),
+                Encoding::Utf16Le if length % 2 == 1 => {
+                    let mut high = [0; 1];
+                    if reader.read(&mut high).await? == 0 {
+                        break;
+                    }
+                    bytes.push(high[0]);
+                    if high[0] == 0 {
+                        break;
+                    }
+                }
+                Encoding::Utf16Le => (),
+            }
+        }
+        Ok(bytes.len() - start)
+    }
+
+    /// Removes the code unit with value `terminator` from the end of `bytes`, if it is there.
+    pub(crate) fn strip_terminator(self, bytes: &mut Vec<u8>, terminator: u8) {
+        let unit: &[u8] = match self {
+            Encoding::Utf8 | Encoding::Latin1 => &[terminator],
+            Encoding::Utf16Le => &[terminator, 0],
+            Encoding::Utf16Be => &[0, terminator],
+        };
+        if bytes.ends_with(unit) {
+            bytes.truncate(bytes.len() - unit.len());
+        }
+    }
+
+    /// Decodes `bytes` into a `String`, dropping a leading byte order mark.
+    pub(crate) fn decode(self, bytes: Vec<u8>) -> io::Result<String> {
+        let invalid = || {
+            let message = format!("stream did not contain valid {}", self.name());
+            io::Error::new(io::ErrorKind::InvalidData, message)
+        };
+        let decoded = match self {
+            Encoding::Utf8 => String::from_utf8(bytes).map_err(|_| invalid())?,
+            Encoding::Latin1 => bytes.into_iter().map(char::from).collect(),
+            Encoding::Utf16Le | Encoding::Utf16Be => {
+                if bytes.len() % 2 == 1 {
+                    return Err(invalid());
+                }
+                let units: Vec<u16> = bytes
+                    .chunks_exact(2)
+                    .map(|unit| match self {
+                        Encoding::Utf16Be => u16::from_be_bytes([unit[0], unit[1]]),
+                        _ => u16::from_le_bytes([unit[0], unit[1]]),
+                    })
+                    .collect();
+                String::from_utf16(&units).map_err(|_| invalid())?
+            }
+        };
+        Ok(match decoded.strip_prefix('\u{feff}') {
+            Some(decoded) => decoded.to_string(),
+            None => decoded,
+        })
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Encoding::Utf8 => "UTF-8",
+            Encoding::Utf16Le | Encoding::Utf16Be => "UTF-16",
+            Encoding::Latin1 => "Latin-1",
+        }
+    }
+}
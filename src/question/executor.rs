@@ -1,7 +1,20 @@
 use std::time::Duration;
 
+/// How a question is timed.
+///
+/// Set by the methods [`timeout`], [`timeout_input_only`] and [`idle_timeout`].
+///
+/// [`timeout`]: struct.QuestionBuilder.html#method.timeout
+/// [`timeout_input_only`]: struct.QuestionBuilder.html#method.timeout_input_only
+/// [`idle_timeout`]: struct.QuestionBuilder.html#method.idle_timeout
 #[derive(Debug, Clone, PartialEq, PartialOrd, Ord, Eq)]
 pub enum Executor {
+    /// There is no time limit.
     None,
+    /// The whole question has a time limit.
     Timeout(Duration),
+    /// Only reading input has a time limit.
+    TimeoutInputOnly(Duration),
+    /// Only a pause while reading input has a time limit.
+    IdleTimeout(Duration),
 }
@@ -0,0 +1,37 @@
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// Primitive integer types, like `u8` or `i64`.
+///
+/// Related to the method [`step`]. This trait is sealed, it can not be implemented
+/// outside this crate.
+///
+/// [`step`]: struct.QuestionBuilder.html#method.step
+pub trait Integer: sealed::Sealed + Copy + PartialEq {
+    /// Whether the value is zero.
+    fn is_zero(self) -> bool;
+
+    /// Whether the value is a multiple of the non-zero `step`.
+    ///
+    /// Unlike `self % step`, this does not overflow for `MIN` and `-1`.
+    fn is_multiple(self, step: Self) -> bool;
+}
+
+macro_rules! impl_integer {
+    ($($t:ty),*) => {$(
+        impl sealed::Sealed for $t {}
+
+        impl Integer for $t {
+            fn is_zero(self) -> bool {
+                self == 0
+            }
+
+            fn is_multiple(self, step: Self) -> bool {
+                self.checked_rem(step).map_or(true, |rem| rem == 0)
+            }
+        }
+    )*};
+}
+
+impl_integer!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
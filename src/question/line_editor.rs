@@ -0,0 +1,224 @@
+use async_std::{
+    future::TimeoutError,
+    io::{
+        prelude::{ReadExt, WriteExt},
+        Read, Write,
+    },
+};
+use std::{marker::Unpin, sync::Arc, time::Duration};
+
+/// Save the cursor position.
+const SAVE: &str = "\x1b7";
+/// Restore the saved cursor position and clear everything after it.
+const RESTORE_AND_CLEAR: &str = "\x1b8\x1b[J";
+/// Move one line down and back up, scrolling if needed, so there is room for the preview.
+const RESERVE_LINE: &str = "\x1bD\x1bM";
+
+/// What a byte did to the line being edited.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Event {
+    Edited,
+    Ignored,
+    Enter,
+    Eof,
+}
+
+/// Part of an escape sequence being skipped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Escape {
+    None,
+    Started,
+    Csi,
+    Ss3,
+}
+
+/// Line being typed in a terminal in raw mode.
+#[derive(Debug)]
+struct LineEditor {
+    line: String,
+    pending: Vec<u8>,
+    escape: Escape,
+    terminator: u8,
+}
+
+impl LineEditor {
+    fn new(terminator: u8, line: &str) -> Self {
+        LineEditor {
+            line: line.to_string(),
+            pending: Vec::new(),
+            escape: Escape::None,
+            terminator,
+        }
+    }
+
+    fn feed(&mut self, byte: u8) -> Event {
+        match self.escape {
+            Escape::None => (),
+            Escape::Started => {
+                self.escape = match byte {
+                    b'[' => Escape::Csi,
+                    b'O' => Escape::Ss3,
+                    _ => Escape::None,
+                };
+                return Event::Ignored;
+            }
+            Escape::Csi => {
+                if (0x40..=0x7e).contains(&byte) {
+                    self.escape = Escape::None;
+                }
+                return Event::Ignored;
+            }
+            Escape::Ss3 => {
+                self.escape = Escape::None;
+                return Event::Ignored;
+            }
+        }
+        match byte {
+            b'\r' | b'\n' => Event::Enter,
+            byte if byte == self.terminator => Event::Enter,
+            0x04 if self.line.is_empty() => Event::Eof,
+            0x7f | 0x08 => {
+                self.pending.clear();
+                match self.line.pop() {
+                    Some(_) => Event::Edited,
+                    None => Event::Ignored,
+                }
+            }
+            0x1b => {
+                self.escape = Escape::Started;
+                Event::Ignored
+            }
+            byte if byte < 0x20 => Event::Ignored,
+            byte => {
+                self.pending.push(byte);
+                match std::str::from_utf8(&self.pending) {
+                    Ok(character) => {
+                        self.line.push_str(character);
+                        self.pending.clear();
+                        Event::Edited
+                    }
+                    Err(e) if e.error_len().is_some() => {
+                        self.pending.clear();
+                        Event::Ignored
+                    }
+                    Err(_) => Event::Ignored,
+                }
+            }
+        }
+    }
+}
+
+/// How the line is displayed while it is typed.
+#[derive(Clone)]
+pub(crate) enum Echo {
+    /// The line, as it is typed.
+    Plain,
+    /// The line, with a preview of it in the line below.
+    Preview(Arc<dyn Fn(&str) -> String + Send + Sync>),
+    /// Only the number of characters of the line.
+    MaskedLength,
+}
+
+impl Echo {
+    /// Returns what is displayed in place of `line` and in the line below it.
+    fn render(&self, line: &str) -> (String, String) {
+        match self {
+            Echo::Plain => (line.to_string(), String::new()),
+            Echo::Preview(preview) => (line.to_string(), preview(line)),
+            Echo::MaskedLength => (format!("({} chars)", line.chars().count()), String::new()),
+        }
+    }
+
+    /// Returns what is left displayed in place of `line` once it is finished.
+    fn finish(&self, line: &str) -> String {
+        match self {
+            Echo::Plain | Echo::Preview(_) => line.to_string(),
+            Echo::MaskedLength => String::new(),
+        }
+    }
+}
+
+/// Reads a line byte by byte, displaying it as set by `echo`.
+///
+/// The terminal is expected to be in raw mode. As with `read_until`, the bytes read are
+/// appended to `bytes`, ending with `terminator` unless the end of the input was reached.
+/// The line starts as `prefill`, which the user can edit.
+pub(crate) async fn read_line<R, W>(
+    reader: &mut R,
+    writer: &mut W,
+    terminator: u8,
+    bytes: &mut Vec<u8>,
+    echo: &Echo,
+    prefill: &str,
+    idle: Option<Duration>,
+) -> Result<std::io::Result<usize>, TimeoutError>
+where
+    R: Read + Unpin,
+    W: Write + Unpin,
+{
+    let mut editor = LineEditor::new(terminator, prefill);
+    if let Err(e) = draw(writer, &[RESERVE_LINE, SAVE]).await {
+        return Ok(Err(e));
+    }
+    if !editor.line.is_empty() {
+        if let Err(e) = redraw(writer, echo, &editor.line).await {
+            return Ok(Err(e));
+        }
+    }
+    let event = loop {
+        let mut byte = [0; 1];
+        let read = match idle {
+            Some(idle) => async_std::future::timeout(idle, reader.read(&mut byte)).await?,
+            None => reader.read(&mut byte).await,
+        };
+        let event = match read {
+            Ok(0) => Event::Eof,
+            Ok(_) => editor.feed(byte[0]),
+            Err(e) => return Ok(Err(e)),
+        };
+        match event {
+            Event::Edited => {
+                if let Err(e) = redraw(writer, echo, &editor.line).await {
+                    return Ok(Err(e));
+                }
+            }
+            Event::Ignored => (),
+            Event::Enter | Event::Eof => break event,
+        }
+    };
+    let ending = match editor.line.is_empty() && event == Event::Eof {
+        true => "",
+        false => "\r\n",
+    };
+    let shown = echo.finish(&editor.line);
+    if let Err(e) = draw(writer, &[RESTORE_AND_CLEAR, &shown, ending]).await {
+        return Ok(Err(e));
+    }
+    let start = bytes.len();
+    bytes.extend_from_slice(editor.line.as_bytes());
+    if event == Event::Enter {
+        bytes.push(terminator);
+    }
+    Ok(Ok(bytes.len() - start))
+}
+
+/// Displays `line` as set by `echo`, leaving the cursor at its end.
+async fn redraw<W: Write + Unpin>(writer: &mut W, echo: &Echo, line: &str) -> std::io::Result<()> {
+    let (shown, below) = echo.render(line);
+    let back = match shown.chars().count() {
+        0 => String::new(),
+        width => format!("\x1b[{}C", width),
+    };
+    let below = match below.is_empty() {
+        true => below,
+        false => format!("\r\n{}", below),
+    };
+    draw(writer, &[RESTORE_AND_CLEAR, &shown, &below, "\x1b8", &back]).await
+}
+
+async fn draw<W: Write + Unpin>(writer: &mut W, parts: &[&str]) -> std::io::Result<()> {
+    for part in parts {
+        writer.write_all(part.as_bytes()).await?;
+    }
+    writer.flush().await
+}
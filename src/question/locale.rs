@@ -0,0 +1,35 @@
+/// Language of the answers to yes/no questions.
+///
+/// Related to the method [`locale`].
+///
+/// [`locale`]: struct.QuestionBuilder.html#method.locale
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Locale {
+    /// "yes", "y", "true", "t" and "no", "n", "false", "f".
+    #[default]
+    English,
+    /// "ja", "j" and "nein", "n".
+    German,
+    /// "sí", "s" and "no", "n".
+    Spanish,
+    /// "oui", "o" and "non", "n".
+    French,
+    /// "sì", "s" and "no", "n".
+    Italian,
+    /// "sim", "s" and "não", "n".
+    Portuguese,
+}
+
+impl Locale {
+    /// Returns the affirmative and negative answers, in lowercase and without accents.
+    pub(crate) fn tokens(&self) -> (&'static [&'static str], &'static [&'static str]) {
+        match self {
+            Locale::English => (&["yes", "y", "true", "t"], &["no", "n", "false", "f"]),
+            Locale::German => (&["ja", "j"], &["nein", "n"]),
+            Locale::Spanish => (&["si", "s"], &["no", "n"]),
+            Locale::French => (&["oui", "o"], &["non", "n"]),
+            Locale::Italian => (&["si", "s"], &["no", "n"]),
+            Locale::Portuguese => (&["sim", "s"], &["nao", "n"]),
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use async_std::io::{BufReader, BufWriter};
+
+use super::{
+    AsyncTest, Cancel, DefaultChain, Feedback, OutcomeLog, Parser, Predicate, QuestionConfig,
+    Round, Template, Test, TimedFeedback,
+};
+
+/// All the settings of a [`QuestionBuilder`], obtained by [`into_parts`].
+///
+/// Useful to inspect or replace specific settings, and then rebuild the question
+/// with [`from_parts`]. Each field corresponds to the method with the same name.
+///
+/// [`QuestionBuilder`]: struct.QuestionBuilder.html
+/// [`into_parts`]: struct.QuestionBuilder.html#method.into_parts
+/// [`from_parts`]: struct.QuestionBuilder.html#method.from_parts
+#[non_exhaustive]
+pub struct QuestionParts<T, R, W> {
+    /// Buffered reader, including input read but not yet consumed.
+    pub reader: BufReader<R>,
+    /// Buffered writer.
+    pub writer: BufWriter<W>,
+    /// Settings that do not depend on the type of the value.
+    pub config: QuestionConfig,
+    /// Value for empty input.
+    pub default_value: Option<T>,
+    /// Sources of the default value, resolved when asking.
+    pub default_chain: Option<DefaultChain<T>>,
+    /// Feedback for the accepted value.
+    pub feedback: Feedback<T>,
+    /// Feedback given the time the user took to answer, replacing the feedback.
+    pub feedback_with_timing: Option<TimedFeedback<T>>,
+    /// Parser of the input.
+    pub parser: Parser<T>,
+    /// Whether the errors of the parser are displayed.
+    pub parser_feedback: bool,
+    /// Tests over the value, and whether their errors are displayed.
+    pub tests: Vec<(Test<T>, bool)>,
+    /// Message of the second yes/no question, and the value upon a no.
+    pub double_confirm: Option<(String, Cancel<T>)>,
+    /// Message asking whether the value is correct.
+    pub confirm_value: Option<Template<T>>,
+    /// Predicate of the values that restore the attempts.
+    pub reset_attempts_on: Option<Predicate<T>>,
+    /// Predicate of the values accepted without running the tests.
+    pub accept_if: Option<Predicate<T>>,
+    /// Rounding of the accepted value.
+    pub round_to: Option<Round<T>>,
+    /// Writer of the summary of the outcome, and formatter of the value.
+    pub log_outcome: Option<OutcomeLog<T>>,
+    /// Asynchronous tests over the value.
+    pub async_tests: Vec<AsyncTest<T>>,
+}
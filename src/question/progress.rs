@@ -0,0 +1,35 @@
+use super::Notify;
+
+/// Handle to report progress while an asynchronous test runs.
+///
+/// Each report is passed to the callback set by [`progress`], if any.
+///
+/// Related to the method [`async_test`].
+///
+/// [`progress`]: struct.QuestionBuilder.html#method.progress
+/// [`async_test`]: struct.QuestionBuilder.html#method.async_test
+#[derive(Clone, Default)]
+pub struct Progress {
+    callback: Option<Notify>,
+}
+
+impl Progress {
+    pub(crate) fn new(callback: Option<Notify>) -> Self {
+        Progress { callback }
+    }
+
+    /// Reports `message`, like `"checking availability..."`.
+    pub fn report(&self, message: impl AsRef<str>) {
+        if let Some(callback) = &self.callback {
+            callback(message.as_ref());
+        }
+    }
+}
+
+impl std::fmt::Debug for Progress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Progress")
+            .field("callback", &self.callback.is_some())
+            .finish()
+    }
+}
@@ -0,0 +1,17 @@
+use std::time::Duration;
+
+/// How many attempts the user has to answer, and how long to wait between them.
+///
+/// Related to the method [`retry_policy`].
+///
+/// [`retry_policy`]: struct.QuestionBuilder.html#method.retry_policy
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RetryPolicy {
+    /// A fixed number of attempts.
+    Fixed(usize),
+    /// A fixed number of attempts, waiting `delay` after each failed one.
+    WithDelay { attempts: usize, delay: Duration },
+    /// Infinite attempts.
+    #[default]
+    Unlimited,
+}
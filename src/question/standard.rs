@@ -3,7 +3,7 @@ use async_std::{
     io::{Stdin, Stdout},
 };
 use core::str::FromStr;
-use std::error::Error;
+use std::{error::Error, io::IsTerminal};
 
 use crate::QuestionBuilder;
 
@@ -17,7 +17,7 @@ where
     E: Error + Send + Sync + 'static,
 {
     fn from(parser: F) -> Self {
-        QuestionBuilder::new(io::stdin(), io::stdout(), parser)
+        QuestionBuilder::new(io::stdin(), io::stdout(), parser).standard()
     }
 }
 
@@ -27,6 +27,19 @@ where
     <T as FromStr>::Err: Send + Sync + Error + 'static,
 {
     fn default() -> Self {
-        StdQuestionBuilder::new_fromstr(io::stdin(), io::stdout())
+        StdQuestionBuilder::new_fromstr(io::stdin(), io::stdout()).standard()
     }
 }
+
+impl<T> StdQuestionBuilder<T> {
+    /// Marks the reader as the standard input, detecting whether it is a terminal.
+    fn standard(mut self) -> Self {
+        self.config.reads_stdin = true;
+        self.terminal(is_terminal())
+    }
+}
+
+/// Returns `true` if both the standard input and output are a terminal.
+fn is_terminal() -> bool {
+    std::io::stdin().is_terminal() && std::io::stdout().is_terminal()
+}
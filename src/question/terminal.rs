@@ -0,0 +1,59 @@
+/// Changes the mode of the terminal in the standard input until dropped.
+pub(crate) struct ModeGuard {
+    #[cfg(unix)]
+    original: libc::termios,
+}
+
+impl ModeGuard {
+    /// Disables echo, returning a guard that restores the original mode.
+    pub(crate) fn no_echo() -> std::io::Result<Self> {
+        #[cfg(unix)]
+        return Self::new(libc::ECHO, libc::ECHONL);
+        #[cfg(not(unix))]
+        return Err(std::io::ErrorKind::Unsupported.into());
+    }
+
+    /// Disables echo and line buffering, so input is read as it is typed,
+    /// returning a guard that restores the original mode.
+    pub(crate) fn raw() -> std::io::Result<Self> {
+        #[cfg(unix)]
+        return Self::new(libc::ECHO | libc::ICANON, 0);
+        #[cfg(not(unix))]
+        return Err(std::io::ErrorKind::Unsupported.into());
+    }
+
+    #[cfg(unix)]
+    fn new(disable: libc::tcflag_t, enable: libc::tcflag_t) -> std::io::Result<Self> {
+        let mut termios = std::mem::MaybeUninit::<libc::termios>::uninit();
+        // SAFETY: `tcgetattr` initializes `termios` if it succeeds.
+        let original = unsafe {
+            if libc::tcgetattr(libc::STDIN_FILENO, termios.as_mut_ptr()) != 0 {
+                return Err(std::io::Error::last_os_error());
+            }
+            termios.assume_init()
+        };
+        let mut mode = original;
+        mode.c_lflag &= !disable;
+        mode.c_lflag |= enable;
+        // SAFETY: `mode` is a valid configuration obtained from `tcgetattr`.
+        if unsafe { libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &mode) } != 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+        Ok(ModeGuard { original })
+    }
+
+    /// Returns `true` if the mode of the terminal can be changed on this platform.
+    pub(crate) fn is_supported() -> bool {
+        cfg!(unix)
+    }
+}
+
+impl Drop for ModeGuard {
+    fn drop(&mut self) {
+        #[cfg(unix)]
+        // SAFETY: `original` is a valid configuration obtained from `tcgetattr`.
+        unsafe {
+            libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &self.original);
+        }
+    }
+}
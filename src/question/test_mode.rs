@@ -0,0 +1,13 @@
+/// How many failing tests are reported for a single input.
+///
+/// Related to the method [`str_test_mode`].
+///
+/// [`str_test_mode`]: struct.QuestionBuilder.html#method.str_test_mode
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TestMode {
+    /// Stop at the first failing test, displaying only its message.
+    #[default]
+    FirstFailure,
+    /// Run all tests, displaying the message of every failing one.
+    AllFailures,
+}
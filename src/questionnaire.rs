@@ -0,0 +1,247 @@
+use async_std::io::{Read, Write};
+use std::{
+    any::Any,
+    collections::HashMap,
+    marker::Unpin,
+    sync::{Arc, RwLock},
+    time::{Duration, Instant},
+};
+
+use crate::{error::ProcessingError, QuestionBuilder};
+
+type Answer = Arc<dyn Any + Send + Sync>;
+
+/// Answers previously accepted in a [`Questionnaire`], by name.
+///
+/// Cloning a `Context` gives a handle to the same answers.
+///
+/// [`Questionnaire`]: struct.Questionnaire.html
+#[derive(Clone, Default)]
+pub struct Context {
+    answers: Arc<RwLock<HashMap<String, Answer>>>,
+}
+
+impl Context {
+    /// Constructs a new, empty, `Context`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the answer named `name`,
+    /// or `None` if there is no such answer or it is not of type `T`.
+    pub fn get<T>(&self, name: &str) -> Option<T>
+    where
+        T: Clone + 'static,
+    {
+        self.read()
+            .get(name)
+            .and_then(|answer| answer.downcast_ref::<T>())
+            .cloned()
+    }
+
+    /// Returns `true` if there is an answer named `name`.
+    pub fn contains(&self, name: &str) -> bool {
+        self.read().contains_key(name)
+    }
+
+    /// Stores `value` as the answer named `name`, replacing any previous one.
+    pub fn insert<T>(&self, name: impl ToString, value: T)
+    where
+        T: Send + Sync + 'static,
+    {
+        self.write().insert(name.to_string(), Arc::new(value));
+    }
+
+    /// Replaces all answers by the ones in `other`.
+    pub(crate) fn set(&self, other: &Context) {
+        if Arc::ptr_eq(&self.answers, &other.answers) {
+            return;
+        }
+        let answers = other.read().clone();
+        *self.write() = answers;
+    }
+
+    fn read(&self) -> std::sync::RwLockReadGuard<'_, HashMap<String, Answer>> {
+        self.answers.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn write(&self) -> std::sync::RwLockWriteGuard<'_, HashMap<String, Answer>> {
+        self.answers.write().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl std::fmt::Debug for Context {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_set().entries(self.read().keys()).finish()
+    }
+}
+
+/// Sequence of questions, where later questions can depend on previous answers.
+///
+/// Each answer is stored in a [`Context`] under a name.
+/// Questions can access it through the methods [`test_with_context`] and [`parser_with_context`].
+///
+/// # Examples
+///
+/// An end date that must come after the start date.
+/// ```no_run
+/// # async_std::task::block_on(async {
+/// use chrono::NaiveDate;
+///
+/// let mut questionnaire = asking::Questionnaire::new();
+/// let start: NaiveDate = questionnaire
+///     .ask("start", asking::date().message("Start date: "))
+///     .await?;
+/// let end: NaiveDate = questionnaire
+///     .ask(
+///         "end",
+///         asking::date().message("End date: ").test_with_context(
+///             |end: &NaiveDate, context| Some(*end) > context.get("start"),
+///             "The end date must come after the start date.",
+///         ),
+///     )
+///     .await?;
+/// # Ok::<(), asking::error::ProcessingError>(())
+/// # });
+/// ```
+///
+/// [`Context`]: struct.Context.html
+/// [`test_with_context`]: struct.QuestionBuilder.html#method.test_with_context
+/// [`parser_with_context`]: struct.QuestionBuilder.html#method.parser_with_context
+#[derive(Debug, Default)]
+pub struct Questionnaire {
+    context: Context,
+    total_timeout: Option<Duration>,
+    start: Option<Instant>,
+}
+
+impl Questionnaire {
+    /// Constructs a new `Questionnaire` with no answers.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set a maximum time to answer all questions, counting from the first one.
+    ///
+    /// Each question gets the time that is left. If it runs out,
+    /// the question being asked returns the error [`Timeout`].
+    ///
+    /// # Remarks
+    ///
+    /// This is independent of the [`timeout`] of each question, which also applies.
+    ///
+    /// [`Timeout`]: error/enum.ProcessingError.html#variant.Timeout
+    /// [`timeout`]: struct.QuestionBuilder.html#method.timeout
+    pub fn total_timeout(mut self, duration: Duration) -> Self {
+        self.total_timeout = Some(duration);
+        self
+    }
+
+    /// Answers accepted so far.
+    pub fn context(&self) -> &Context {
+        &self.context
+    }
+
+    /// Asks `question`, storing the answer under `name`.
+    ///
+    /// Context-aware tests and parsers of `question` see all previous answers.
+    pub async fn ask<T, R, W>(
+        &mut self,
+        name: impl ToString,
+        question: QuestionBuilder<T, R, W>,
+    ) -> Result<T, ProcessingError>
+    where
+        T: Clone + Send + Sync + 'static,
+        R: Read + Unpin,
+        W: Write + Unpin,
+    {
+        question.context_handle().set(&self.context);
+        let answer = match self.total_timeout {
+            Some(duration) => {
+                let start = *self.start.get_or_insert_with(Instant::now);
+                let remaining = duration.saturating_sub(start.elapsed());
+                async_std::future::timeout(remaining, question.ask())
+                    .await
+                    .map_err(|source| ProcessingError::Timeout { source, duration })??
+            }
+            None => question.ask().await?,
+        };
+        self.context.insert(name, answer.clone());
+        Ok(answer)
+    }
+}
+
+/// Asks `first` and then `second`, re-asking `second` until both answers satisfy `predicate`.
+///
+/// This is a two-question version of a [`Questionnaire`] with [`test_with_context`],
+/// for example, for an end that must come after a start.
+///
+/// # Remarks
+///
+/// The cross-check is added as a [`test`] of `second`, so it counts towards its attempts.
+/// There is a default message you might want to change, check out [`combine_with_msg`].
+///
+/// # Examples
+///
+/// ```
+/// # async_std::task::block_on(async {
+/// let (start, end) = asking::combine(
+///     asking::question::<u32>().reader("5\n".as_bytes()),
+///     asking::question::<u32>().reader("7\n".as_bytes()),
+///     |start, end| end > start,
+/// )
+/// .await?;
+/// assert_eq!((start, end), (5, 7));
+/// # Ok::<(), asking::error::ProcessingError>(())
+/// # });
+/// ```
+///
+/// [`Questionnaire`]: struct.Questionnaire.html
+/// [`test_with_context`]: struct.QuestionBuilder.html#method.test_with_context
+/// [`test`]: struct.QuestionBuilder.html#method.test
+/// [`combine_with_msg`]: fn.combine_with_msg.html
+pub async fn combine<T1, T2, R1, W1, R2, W2, F>(
+    first: QuestionBuilder<T1, R1, W1>,
+    second: QuestionBuilder<T2, R2, W2>,
+    predicate: F,
+) -> Result<(T1, T2), ProcessingError>
+where
+    T1: Clone + Send + Sync + 'static,
+    R1: Read + Unpin,
+    W1: Write + Unpin,
+    R2: Read + Unpin,
+    W2: Write + Unpin,
+    F: Fn(&T1, &T2) -> bool + Send + Sync + 'static,
+{
+    combine_with_msg(first, second, predicate, "The answers are not compatible.").await
+}
+
+/// Asks `first` and then `second`, re-asking `second` until both answers satisfy `predicate`,
+/// displaying a message upon failure.
+///
+/// Check out [`combine`].
+///
+/// [`combine`]: fn.combine.html
+pub async fn combine_with_msg<T1, T2, R1, W1, R2, W2, F, M>(
+    first: QuestionBuilder<T1, R1, W1>,
+    second: QuestionBuilder<T2, R2, W2>,
+    predicate: F,
+    message: M,
+) -> Result<(T1, T2), ProcessingError>
+where
+    T1: Clone + Send + Sync + 'static,
+    R1: Read + Unpin,
+    W1: Write + Unpin,
+    R2: Read + Unpin,
+    W2: Write + Unpin,
+    F: Fn(&T1, &T2) -> bool + Send + Sync + 'static,
+    M: ToString + Send + Sync + 'static,
+{
+    let first_answer = first.ask().await?;
+    let answer = first_answer.clone();
+    let second_answer = second
+        .test_with_msg(move |value: &T2| predicate(&answer, value), message)
+        .ask()
+        .await?;
+    Ok((first_answer, second_answer))
+}
@@ -0,0 +1,18 @@
+//! Tests the matching example `live_preview`.
+
+use assert_cmd::cmd::Command;
+
+#[test]
+fn no_terminal() -> eyre::Result<()> {
+    let output = Command::cargo_bin("examples//live_preview")?
+        .write_stdin("2022-01-01\n")
+        .timeout(std::time::Duration::from_secs(1))
+        .unwrap();
+
+    assert_eq!(
+        std::str::from_utf8(&output.stdout)?,
+        "Please input a date: You chose Saturday.\n"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,18 @@
+//! Tests the matching example `prefill`.
+
+use assert_cmd::cmd::Command;
+
+#[test]
+fn no_terminal() -> eyre::Result<()> {
+    let output = Command::cargo_bin("examples//prefill")?
+        .write_stdin("asking\n")
+        .timeout(std::time::Duration::from_secs(1))
+        .unwrap();
+
+    assert_eq!(
+        std::str::from_utf8(&output.stdout)?,
+        "Project name: Creating asking.\n"
+    );
+
+    Ok(())
+}
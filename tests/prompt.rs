@@ -0,0 +1,23 @@
+//! Tests the matching example `prompt`.
+
+use assert_cmd::cmd::Command;
+
+#[test]
+fn input() -> eyre::Result<()> {
+    let output = Command::cargo_bin("examples//prompt")?
+        .write_stdin("old\n42\n")
+        .timeout(std::time::Duration::from_secs(1))
+        .unwrap();
+
+    assert_eq!(
+        std::str::from_utf8(&output.stdout)?,
+        "\
+            [prompt] How old are you? \
+            [prompt] Please, use a number.\n\
+            [log] You are 42 years old.\n\
+        "
+        .to_string()
+    );
+
+    Ok(())
+}
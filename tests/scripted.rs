@@ -0,0 +1,22 @@
+//! Tests the matching example `scripted`.
+
+use assert_cmd::cmd::Command;
+
+#[test]
+fn input() -> eyre::Result<()> {
+    let output = Command::cargo_bin("examples//scripted")?
+        .timeout(std::time::Duration::from_secs(1))
+        .unwrap();
+
+    assert_eq!(
+        std::str::from_utf8(&output.stdout)?,
+        "\
+            How are you? \
+            I hope not! Try again.\n\
+            I am good too!\n\
+        "
+        .to_string()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,18 @@
+//! Tests the matching example `tcp`.
+
+use assert_cmd::cmd::Command;
+
+#[test]
+fn input() -> eyre::Result<()> {
+    let output = Command::cargo_bin("examples//tcp")?
+        .write_stdin("Ferris\n")
+        .timeout(std::time::Duration::from_secs(1))
+        .unwrap();
+
+    assert_eq!(
+        std::str::from_utf8(&output.stdout)?,
+        "What is your name? Hello, Ferris!\nThe server greeted Ferris.\n"
+    );
+
+    Ok(())
+}